@@ -1,5 +1,9 @@
 pub use egui as api;
 
+pub mod plugin;
+
+use std::time::Duration;
+
 use egui::{ahash::HashMap, ClippedPrimitive, Context, RawInput, TexturesDelta, ViewportId};
 use egui_wgpu::{Renderer, ScreenDescriptor};
 use egui_winit::{
@@ -9,7 +13,10 @@ use egui_winit::{
     },
     State,
 };
-use nuum_core::{event::Render, Controller};
+use nuum_core::{
+    event::{Propagation, Render},
+    Controller,
+};
 use nuum_gpu::{
     surface::GpuSurface,
     wgpu::{
@@ -29,6 +36,7 @@ pub struct WindowState {
     state: State,
     input: RawInput,
     screen: ScreenDescriptor,
+    repaint_delay: Option<Duration>,
 }
 
 #[derive(Default)]
@@ -37,10 +45,11 @@ pub struct EguiRenderer {
     ctx: Context,
 }
 
-impl<T: EguiRenderData, Inner: for<'c, 'd> Controller<RenderEguiEvent<'c, 'd, T>>>
-    NativeRenderer<T, WinPlatformEvent<'_>, Inner> for EguiRenderer
+impl<T: EguiRenderData, U, Inner: for<'c, 'd> Controller<RenderEguiEvent<'c, 'd, T>>>
+    NativeRenderer<T, WinPlatformEvent<'_, U>, Inner> for EguiRenderer
 {
-    fn on_platform_event(&mut self, event: &mut WinPlatformEvent<'_>) {
+    fn on_platform_event(&mut self, event: &mut WinPlatformEvent<'_, U>) {
+        let mut consumed = false;
         match &event.kind {
             WinPlatformEventKind::WindowEvent {
                 window_id,
@@ -54,11 +63,17 @@ impl<T: EguiRenderData, Inner: for<'c, 'd> Controller<RenderEguiEvent<'c, 'd, T>
                         self.ctx.viewport_id(),
                         window,
                     );
-                    let _ = window_state.state.on_window_event(window, window_event);
+                    consumed = window_state
+                        .state
+                        .on_window_event(window, window_event)
+                        .consumed;
 
                     let (w, h): (u32, u32) = window.inner_size().into();
                     if matches!(window_event, WindowEvent::RedrawRequested) && w > 0 && h > 0 {
+                        // The input of a redraw deferred by the frame limit is not consumed yet
+                        let pending = std::mem::take(&mut window_state.input.events);
                         window_state.input = window_state.state.take_egui_input(window);
+                        window_state.input.events.splice(0..0, pending);
                         window_state.screen = ScreenDescriptor {
                             size_in_pixels: [w, h],
                             pixels_per_point: window.scale_factor() as f32,
@@ -79,10 +94,16 @@ impl<T: EguiRenderData, Inner: for<'c, 'd> Controller<RenderEguiEvent<'c, 'd, T>
             },
             _ => (),
         };
+
+        // Stop the input from reaching ports behind the egui ui
+        if consumed {
+            event.set_handled();
+        }
     }
 
     fn render_port(&mut self, event: &mut RenderEvent<T>, inner: &mut Inner) {
         if let Some(window_state) = self.states.get_mut(&event.window_id()) {
+            let _span = tracing::debug_span!("egui_payload", window = ?event.window_id()).entered();
             let full_output = self
                 .ctx
                 .run(std::mem::take(&mut window_state.input), |ctx| {
@@ -91,9 +112,15 @@ impl<T: EguiRenderData, Inner: for<'c, 'd> Controller<RenderEguiEvent<'c, 'd, T>
                             base: &mut event.inner,
                             egui: ctx.clone(),
                         },
-                        dt: event.dt,
+                        time: event.time,
                     });
                 });
+            // Animations and `Context::request_repaint` ask for a repaint, `Duration::MAX` means none
+            window_state.repaint_delay = full_output
+                .viewport_output
+                .get(&ViewportId::ROOT)
+                .map(|viewport| viewport.repaint_delay)
+                .filter(|delay| *delay != Duration::MAX);
             let paint_jobs = self
                 .ctx
                 .tessellate(full_output.shapes, full_output.pixels_per_point);
@@ -110,6 +137,10 @@ impl<T: EguiRenderData, Inner: for<'c, 'd> Controller<RenderEguiEvent<'c, 'd, T>
                 });
         }
     }
+
+    fn redraw_after(&self, window_id: WindowId) -> Option<Duration> {
+        self.states.get(&window_id)?.repaint_delay
+    }
 }
 
 pub type RenderEguiEvent<'a, 'b, T> = Render<EguiRenderInner<'a, 'b, T>>;
@@ -143,6 +174,7 @@ fn window_state_lazy<'a>(
             pixels_per_point: 0.,
             size_in_pixels: [0, 0],
         },
+        repaint_delay: None,
     })
 }
 pub struct EguiRenderPass {
@@ -180,7 +212,7 @@ impl EguiRenderPass {
         gpu: &Gpu,
     ) {
         let Some(render_data) = res.try_access(&self.render_data) else {
-            println!("Warn: No egui render data provided, use the EguiRenderer as native renderer in the RenderPort or remove EguiRenderPass from the RenderGraph.");
+            tracing::warn!("No egui render data provided, use the EguiRenderer as native renderer in the RenderPort or remove EguiRenderPass from the RenderGraph.");
             return;
         };
 
@@ -0,0 +1,37 @@
+use nuum_core::Controller;
+use nuum_plugin::{builtins::RenderPlugin, Plugin, PluginContext};
+use nuum_render_graph::{
+    pass::PassScheduler,
+    res::{ResHandle, ResRegistry},
+};
+
+use crate::{EguiRenderData, EguiRenderPass, EguiRenderPayload, EguiRenderer, RenderEguiEvent};
+
+/// Renders egui on top of every window, after the `"clear"` pass of the [`RenderPlugin`].
+#[derive(Default)]
+pub struct EguiPlugin;
+
+impl<Inner: for<'a, 'b> Controller<RenderEguiEvent<'a, 'b, ResRegistry>> + 'static> Plugin<Inner>
+    for EguiPlugin
+{
+    fn dependencies(&self) -> Vec<&'static str> {
+        vec![RenderPlugin::name()]
+    }
+
+    fn build(self: Box<Self>, ctx: &mut PluginContext<Inner>) {
+        ctx.add_native_renderer(EguiRenderer::default());
+        ctx.add_render_graph(|ctx| {
+            let view = ctx.alloc.frame_view();
+            let payload = ctx.add_resource::<EguiRenderPayload>(None).move_();
+            let pass = EguiRenderPass::new(view.write(), payload, ctx.gpu, ctx.surface);
+            ctx.add_pass("egui", pass.run_after("clear"));
+        });
+    }
+}
+
+impl EguiRenderData for ResRegistry {
+    fn egui_render_payload(&self) -> &ResHandle<EguiRenderPayload> {
+        self.get()
+            .expect("Egui render payload is not registered, add the EguiPlugin")
+    }
+}
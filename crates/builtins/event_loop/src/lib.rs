@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+
+use nuum_core::{event::Update, platform::PlatformEvent, time::Clock, Controller, Port};
+
+pub mod timer;
+
+pub use timer::{TimerEvent, TimerId, TimerPort, Timers};
+
+pub type UpdateEvent = Update<UpdateInfo>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct UpdateInfo {
+    /// Index of this update inside the current platform tick, fixed mode can run several updates per tick.
+    pub step: u32,
+    /// Fraction of a fixed step left unsimulated after this tick, in `[0, 1)`. <br/>
+    /// Use it to interpolate between the last two simulated states when rendering. Always `0` in variable mode.
+    pub alpha: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum UpdateMode {
+    /// One update per platform tick, `dt` is the real elapsed time clamped to `max_dt`.
+    Variable { max_dt: Duration },
+    /// Updates are run with a constant `dt` of `step`, catching up on elapsed time. <br/>
+    /// At most `max_steps` updates are run per tick, the remaining time is dropped to avoid a spiral of death.
+    Fixed { step: Duration, max_steps: u32 },
+}
+
+/// Turns platform update events into [`UpdateEvent`]s for the inner controller.
+pub struct EventLoopPort {
+    pub mode: UpdateMode,
+    clock: Clock,
+    last_tick: Option<Instant>,
+    accumulator: Duration,
+}
+
+impl Default for EventLoopPort {
+    fn default() -> Self {
+        Self::variable(Duration::from_millis(250))
+    }
+}
+
+impl EventLoopPort {
+    pub fn new(mode: UpdateMode) -> Self {
+        Self {
+            mode,
+            clock: Clock::new(),
+            last_tick: None,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    pub fn variable(max_dt: Duration) -> Self {
+        Self::new(UpdateMode::Variable { max_dt })
+    }
+
+    pub fn fixed(step: Duration, max_steps: u32) -> Self {
+        assert!(
+            !step.is_zero(),
+            "Fixed update step must be greater than zero"
+        );
+        Self::new(UpdateMode::Fixed { step, max_steps })
+    }
+
+    /// Advances `clock` instead of a private one, e.g. to pause updates from a controller. <br/>
+    /// In fixed mode the clock advances by `step` per update, scaling changes the `dt` of updates, not their count.
+    /// The clock must not be advanced by another port, see [`Clock`].
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Interpolation factor of the last tick, see [`UpdateInfo::alpha`].
+    pub fn alpha(&self) -> f32 {
+        match self.mode {
+            UpdateMode::Variable { .. } => 0.,
+            UpdateMode::Fixed { step, .. } => self.accumulator.as_secs_f32() / step.as_secs_f32(),
+        }
+    }
+
+    fn tick(&mut self, platform_dt: Option<Duration>, inner: &mut impl Controller<UpdateEvent>) {
+        let now = Instant::now();
+        let measured = self.last_tick.replace(now).map(|last| now - last);
+        let elapsed = platform_dt.or(measured).unwrap_or_default();
+
+        match self.mode {
+            UpdateMode::Variable { max_dt } => inner.run(Update {
+                inner: UpdateInfo { step: 0, alpha: 0. },
+                time: self.clock.advance(elapsed.min(max_dt)),
+            }),
+            UpdateMode::Fixed { step, max_steps } => {
+                self.accumulator += elapsed;
+
+                let pending = u32::try_from(self.accumulator.as_nanos() / step.as_nanos())
+                    .unwrap_or(u32::MAX);
+                self.accumulator -= step * pending;
+                // Only left past u32::MAX pending steps, dropped as any step beyond max_steps
+                if self.accumulator >= step {
+                    self.accumulator = Duration::ZERO;
+                }
+
+                let alpha = self.alpha();
+                for i in 0..pending.min(max_steps) {
+                    inner.run(Update {
+                        inner: UpdateInfo { step: i, alpha },
+                        time: self.clock.advance(step),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl<'a, E: PlatformEvent, I: Controller<UpdateEvent>> Port<'a, E, I> for EventLoopPort {
+    fn port(&mut self, event: &mut E, inner: &mut I) {
+        if event.is_update() {
+            self.tick(event.dt(), inner);
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
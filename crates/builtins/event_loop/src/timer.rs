@@ -0,0 +1,165 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use nuum_core::{platform::PlatformEvent, time::Clock, Controller, Port};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+/// Sent to the inner controller when a timer fires.
+#[derive(Debug, Clone)]
+pub struct TimerEvent<T> {
+    pub id: TimerId,
+    pub payload: T,
+}
+
+struct TimerEntry<T> {
+    id: TimerId,
+    due: Duration,
+    period: Option<Duration>,
+    payload: T,
+}
+
+struct TimersState<T> {
+    next_id: u64,
+    now: Duration,
+    entries: Vec<TimerEntry<T>>,
+}
+
+/// Shared handle to schedule timers of a [`TimerPort`], clones schedule on the same port.
+pub struct Timers<T>(Arc<Mutex<TimersState<T>>>);
+
+impl<T> Clone for Timers<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Timers<T> {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(TimersState {
+            next_id: 0,
+            now: Duration::ZERO,
+            entries: Vec::new(),
+        })))
+    }
+
+    /// Fires `payload` once, after `delay` of scaled time.
+    pub fn after(&self, delay: Duration, payload: T) -> TimerId {
+        self.schedule(delay, None, payload)
+    }
+
+    /// Fires `payload` every `period` of scaled time until cancelled.
+    pub fn every(&self, period: Duration, payload: T) -> TimerId {
+        assert!(!period.is_zero(), "Timer period must be greater than zero");
+        self.schedule(period, Some(period), payload)
+    }
+
+    /// Returns false if the timer already fired or was cancelled.
+    pub fn cancel(&self, id: TimerId) -> bool {
+        let mut state = self.lock();
+        let len = state.entries.len();
+        state.entries.retain(|e| e.id != id);
+        state.entries.len() != len
+    }
+
+    pub fn is_active(&self, id: TimerId) -> bool {
+        self.lock().entries.iter().any(|e| e.id == id)
+    }
+
+    /// Scaled time left before the timer fires.
+    pub fn remaining(&self, id: TimerId) -> Option<Duration> {
+        let state = self.lock();
+        let entry = state.entries.iter().find(|e| e.id == id)?;
+        Some(entry.due.saturating_sub(state.now))
+    }
+
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn schedule(&self, delay: Duration, period: Option<Duration>, payload: T) -> TimerId {
+        let mut state = self.lock();
+        let id = TimerId(state.next_id);
+        state.next_id += 1;
+        let due = state.now + delay;
+        state.entries.push(TimerEntry {
+            id,
+            due,
+            period,
+            payload,
+        });
+        id
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TimersState<T>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: Clone> Timers<T> {
+    /// Advances the timers to `now`, returning the fired events ordered by due time.
+    fn advance(&self, now: Duration) -> Vec<TimerEvent<T>> {
+        let mut state = self.lock();
+        state.now = now;
+
+        let mut fired = Vec::new();
+        state.entries.retain_mut(|entry| {
+            while entry.due <= now {
+                fired.push((
+                    entry.due,
+                    TimerEvent {
+                        id: entry.id,
+                        payload: entry.payload.clone(),
+                    },
+                ));
+                match entry.period {
+                    Some(period) => entry.due += period,
+                    None => return false,
+                }
+            }
+            true
+        });
+
+        fired.sort_by_key(|(due, _)| *due);
+        fired.into_iter().map(|(_, event)| event).collect()
+    }
+}
+
+/// Fires [`TimerEvent`]s on platform updates, following the scaled elapsed time of `clock`. <br/>
+/// Timers stop while the clock is paused. Place it after the [`crate::EventLoopPort`] sharing the clock, so timers
+/// see the time of the current tick.
+pub struct TimerPort<T> {
+    clock: Clock,
+    timers: Timers<T>,
+}
+
+impl<T> TimerPort<T> {
+    pub fn new(clock: Clock) -> Self {
+        Self {
+            clock,
+            timers: Timers::new(),
+        }
+    }
+
+    pub fn timers(&self) -> Timers<T> {
+        self.timers.clone()
+    }
+}
+
+impl<'a, E: PlatformEvent, T: Clone, I: Controller<TimerEvent<T>>> Port<'a, E, I> for TimerPort<T> {
+    fn port(&mut self, event: &mut E, inner: &mut I) {
+        if event.is_update() {
+            // Fired events are collected first, so the inner controller can schedule or cancel timers
+            for fired in self.timers.advance(self.clock.time().elapsed) {
+                inner.run(fired);
+            }
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use nuum_core::{platform::Platform, Adapter, Controller};
+use nuum_event_loop::{EventLoopPort, UpdateEvent};
+use nuum_headless_platform::{HeadlessPlatform, HeadlessPlatformEventKind, ScriptedEvent};
+
+#[derive(Default)]
+struct Updates(Vec<(u32, f32, Duration)>);
+
+impl Controller<UpdateEvent> for Updates {
+    fn run(&mut self, event: UpdateEvent) {
+        self.0.push((event.step, event.alpha, event.dt()));
+    }
+}
+
+/// `Init` followed by an `AboutToWait` at each time.
+fn script(times: &[Duration]) -> HeadlessPlatform {
+    let events = std::iter::once(ScriptedEvent {
+        time: Some(Duration::ZERO),
+        kind: HeadlessPlatformEventKind::Init,
+    })
+    .chain(times.iter().map(|&time| ScriptedEvent {
+        time: Some(time),
+        kind: HeadlessPlatformEventKind::AboutToWait,
+    }));
+    HeadlessPlatform::new(events)
+}
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn fixed_steps_accumulate_elapsed_time() {
+    let mut app = Adapter::new(EventLoopPort::fixed(ms(10), 5), Updates::default());
+    script(&[ms(25), ms(30)]).run(&mut app);
+
+    assert_eq!(
+        app.inner.0,
+        [(0, 0.5, ms(10)), (1, 0.5, ms(10)), (0, 0., ms(10))]
+    );
+    assert_eq!(app.ports.clock().time().elapsed, ms(30));
+    assert_eq!(app.ports.alpha(), 0.);
+}
+
+#[test]
+fn fixed_steps_beyond_max_steps_are_dropped() {
+    let mut app = Adapter::new(EventLoopPort::fixed(ms(10), 5), Updates::default());
+    script(&[ms(200), ms(215)]).run(&mut app);
+
+    let steps: Vec<u32> = app.inner.0.iter().map(|(step, ..)| *step).collect();
+    assert_eq!(steps, [0, 1, 2, 3, 4, 0]);
+    assert_eq!(app.ports.alpha(), 0.5);
+}
+
+#[test]
+fn fixed_step_count_saturates() {
+    let step = Duration::from_nanos(1);
+    let mut app = Adapter::new(EventLoopPort::fixed(step, 3), Updates::default());
+    // Far more than u32::MAX pending steps, then two steps
+    let long = Duration::from_secs(10);
+    script(&[long, long + step * 2]).run(&mut app);
+
+    assert_eq!(app.inner.0.len(), 5);
+    assert_eq!(app.ports.alpha(), 0.);
+}
+
+#[test]
+fn variable_steps_clamp_dt() {
+    let mut app = Adapter::new(EventLoopPort::variable(ms(100)), Updates::default());
+    script(&[ms(16), ms(516)]).run(&mut app);
+
+    assert_eq!(
+        app.inner.0,
+        [(0, 0., ms(0)), (0, 0., ms(16)), (0, 0., ms(100))]
+    );
+}
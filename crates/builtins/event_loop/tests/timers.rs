@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use nuum_core::{platform::Platform, time::Clock, Adapter, Controller};
+use nuum_event_loop::{EventLoopPort, TimerEvent, TimerPort, Timers, UpdateEvent};
+use nuum_headless_platform::{HeadlessPlatform, HeadlessPlatformEventKind, ScriptedEvent};
+
+type App = Adapter<(EventLoopPort, TimerPort<&'static str>), Game>;
+
+struct Game {
+    timers: Timers<&'static str>,
+    fired: Vec<(Duration, &'static str)>,
+    clock: Clock,
+}
+
+impl Controller<UpdateEvent> for Game {
+    fn run(&mut self, _: UpdateEvent) {}
+}
+
+impl Controller<TimerEvent<&'static str>> for Game {
+    fn run(&mut self, event: TimerEvent<&'static str>) {
+        self.fired.push((self.clock.time().elapsed, event.payload));
+        if event.payload == "chain" {
+            self.timers.after(ms(100), "chained");
+        }
+    }
+}
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+fn app(clock: &Clock) -> App {
+    let timer_port = TimerPort::new(clock.clone());
+    let game = Game {
+        timers: timer_port.timers(),
+        fired: Vec::new(),
+        clock: clock.clone(),
+    };
+    let event_loop = EventLoopPort::variable(ms(1000)).with_clock(clock.clone());
+    Adapter::new((event_loop, timer_port), game)
+}
+
+/// `Init` followed by an `AboutToWait` every 100ms.
+fn run(app: &mut App, ticks: u32) {
+    let events = (0..=ticks).map(|i| ScriptedEvent {
+        time: Some(ms(100) * i),
+        kind: match i {
+            0 => HeadlessPlatformEventKind::Init,
+            _ => HeadlessPlatformEventKind::AboutToWait,
+        },
+    });
+    HeadlessPlatform::new(events).run(app);
+}
+
+#[test]
+fn timers_fire_in_due_order() {
+    let clock = Clock::new();
+    let mut app = app(&clock);
+    let timers = app.inner.timers.clone();
+    timers.every(ms(100), "tick");
+    timers.after(ms(150), "once");
+
+    run(&mut app, 3);
+
+    assert_eq!(
+        app.inner.fired,
+        [
+            (ms(100), "tick"),
+            (ms(200), "once"),
+            (ms(200), "tick"),
+            (ms(300), "tick"),
+        ]
+    );
+}
+
+#[test]
+fn periodic_timers_catch_up_in_one_update() {
+    let clock = Clock::new();
+    let mut app = app(&clock);
+    let id = app.inner.timers.every(ms(40), "tick");
+
+    run(&mut app, 1);
+
+    assert_eq!(app.inner.fired.len(), 2);
+    assert_eq!(app.inner.timers.remaining(id), Some(ms(20)));
+}
+
+#[test]
+fn cancelled_timers_do_not_fire() {
+    let clock = Clock::new();
+    let mut app = app(&clock);
+    let id = app.inner.timers.after(ms(150), "once");
+
+    assert!(app.inner.timers.cancel(id));
+    assert!(!app.inner.timers.cancel(id));
+    run(&mut app, 3);
+
+    assert!(app.inner.fired.is_empty());
+    assert!(!app.inner.timers.is_active(id));
+}
+
+#[test]
+fn timers_scheduled_by_the_controller_fire_later() {
+    let clock = Clock::new();
+    let mut app = app(&clock);
+    app.inner.timers.after(ms(100), "chain");
+
+    run(&mut app, 3);
+
+    assert_eq!(app.inner.fired, [(ms(100), "chain"), (ms(200), "chained")]);
+}
+
+#[test]
+fn timers_stop_while_the_clock_is_paused() {
+    let clock = Clock::new();
+    let mut app = app(&clock);
+    app.inner.timers.after(ms(150), "once");
+    clock.pause();
+
+    run(&mut app, 3);
+
+    assert!(app.inner.fired.is_empty());
+}
@@ -0,0 +1,42 @@
+use nuum_core::{platform::Platform, Adapter, Port};
+use nuum_headless_platform::{
+    builtins::SingleWindowPort, HeadlessPlatform, HeadlessPlatformEvent, HeadlessPlatformEventKind,
+    WindowEvent, WindowId,
+};
+
+fn main() {
+    let mut app = Adapter::new((SingleWindowPort::default(), LogPort), ());
+
+    HeadlessPlatform::new(vec![
+        HeadlessPlatformEventKind::Init,
+        HeadlessPlatformEventKind::AboutToWait,
+        HeadlessPlatformEventKind::WindowEvent {
+            window_id: WindowId(0),
+            window_event: WindowEvent::Resized {
+                width: 1280,
+                height: 720,
+            },
+        },
+        HeadlessPlatformEventKind::AboutToWait,
+        HeadlessPlatformEventKind::WindowEvent {
+            window_id: WindowId(0),
+            window_event: WindowEvent::CloseRequested,
+        },
+        HeadlessPlatformEventKind::AboutToWait,
+    ])
+    .with_max_iterations(10)
+    .run(&mut app);
+}
+
+struct LogPort;
+
+impl<'a, 'b> Port<'a, HeadlessPlatformEvent<'b>, ()> for LogPort {
+    fn port(&mut self, event: &mut HeadlessPlatformEvent, _: &mut ()) {
+        println!(
+            "[{}] {:?} ({} windows)",
+            event.handle.iteration(),
+            event.kind,
+            event.handle.windows().len()
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use nuum_core::Port;
+
+use crate::{HeadlessPlatformEvent, HeadlessPlatformEventKind, WindowAttributes, WindowEvent};
+
+/// Headless counterpart of the window platform `SingleWindowPort`, using a virtual window.
+pub struct SingleWindowPort(pub WindowAttributes);
+
+impl Default for SingleWindowPort {
+    fn default() -> Self {
+        Self(WindowAttributes::default().with_title("Nuum window"))
+    }
+}
+
+impl<'a, 'b, Inner> Port<'a, HeadlessPlatformEvent<'b>, Inner> for SingleWindowPort {
+    fn port(&mut self, event: &mut HeadlessPlatformEvent, _: &mut Inner) {
+        match &event.kind {
+            HeadlessPlatformEventKind::Init => {
+                event.handle.create_window(self.0.clone());
+            }
+            HeadlessPlatformEventKind::WindowEvent {
+                window_id,
+                window_event: WindowEvent::CloseRequested,
+            } => {
+                event.handle.remove_window(*window_id);
+            }
+            _ => (),
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
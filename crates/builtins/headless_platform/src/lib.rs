@@ -0,0 +1,337 @@
+use std::{cell::Cell, time::Duration};
+
+use nuum_core::{
+    event::Propagation,
+    platform::{Platform, PlatformEvent},
+};
+use serde::{Deserialize, Serialize};
+
+pub mod builtins;
+pub mod recording;
+
+pub use recording::Recording;
+
+pub struct HeadlessPlatformEvent<'a> {
+    pub handle: &'a mut HeadlessPlatformHandle,
+    pub kind: HeadlessPlatformEventKind,
+    /// Scripted time elapsed since the previous update, or previous redraw of the same window.
+    pub dt: Option<Duration>,
+    pub handled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HeadlessPlatformEventKind {
+    Init,
+    WindowEvent {
+        window_id: WindowId,
+        window_event: WindowEvent,
+    },
+    DeviceEvent(DeviceEvent),
+    AboutToWait,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WindowEvent {
+    Resized { width: u32, height: u32 },
+    Focused(bool),
+    CloseRequested,
+    RedrawRequested,
+    CursorMoved { x: f64, y: f64 },
+    MouseInput { button: MouseButton, pressed: bool },
+    MouseWheel { dx: f32, dy: f32 },
+    KeyboardInput { key: String, pressed: bool },
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeviceEvent {
+    MouseMotion { dx: f64, dy: f64 },
+}
+
+/// Identifier of a virtual window, ids are given in creation order starting from `0` so scripts can refer to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct WindowId(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct WindowAttributes {
+    pub title: String,
+    pub size: [u32; 2],
+}
+
+impl Default for WindowAttributes {
+    fn default() -> Self {
+        Self {
+            title: "Virtual window".to_string(),
+            size: [800, 600],
+        }
+    }
+}
+
+impl WindowAttributes {
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = [width, height];
+        self
+    }
+}
+
+pub struct VirtualWindow {
+    id: WindowId,
+    pub title: String,
+    pub size: [u32; 2],
+    redraw_requested: Cell<bool>,
+    last_redraw: Option<Duration>,
+}
+
+impl VirtualWindow {
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    pub fn inner_size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// A `RedrawRequested` event will be emitted for this window before the next `AboutToWait`.
+    pub fn request_redraw(&self) {
+        self.redraw_requested.set(true);
+    }
+}
+
+/// Scripted event, optionally stamped with the time at which it happens since the start of the script. <br/>
+/// Stamped events drive the `dt` of update and redraw events, making replays deterministic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptedEvent {
+    pub time: Option<Duration>,
+    pub kind: HeadlessPlatformEventKind,
+}
+
+impl From<HeadlessPlatformEventKind> for ScriptedEvent {
+    fn from(kind: HeadlessPlatformEventKind) -> Self {
+        Self { time: None, kind }
+    }
+}
+
+/// Platform replaying a scripted sequence of events, without any display. <br/>
+/// Each `AboutToWait` in the script ends an iteration.
+/// Once the script is exhausted, the platform keeps emitting `AboutToWait` until `max_iterations` is reached, or stops if there is none.
+pub struct HeadlessPlatform {
+    pub script: Vec<ScriptedEvent>,
+    pub max_iterations: Option<usize>,
+}
+
+impl Default for HeadlessPlatform {
+    fn default() -> Self {
+        Self::new(vec![HeadlessPlatformEventKind::Init])
+    }
+}
+
+impl HeadlessPlatform {
+    pub fn new(script: impl IntoIterator<Item = impl Into<ScriptedEvent>>) -> Self {
+        Self {
+            script: script.into_iter().map(Into::into).collect(),
+            max_iterations: None,
+        }
+    }
+
+    pub fn replay(recording: Recording) -> Self {
+        Self::new(recording.events)
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct HeadlessPlatformHandle {
+    windows: Vec<VirtualWindow>,
+    next_window_id: u64,
+    iteration: usize,
+    exit: Cell<bool>,
+
+    time: Option<Duration>,
+    last_update: Option<Duration>,
+}
+
+impl HeadlessPlatformHandle {
+    pub fn create_window(&mut self, attrs: WindowAttributes) -> &VirtualWindow {
+        let id = WindowId(self.next_window_id);
+        self.next_window_id += 1;
+        self.windows.push(VirtualWindow {
+            id,
+            title: attrs.title,
+            size: attrs.size,
+            redraw_requested: Cell::new(false),
+            last_redraw: None,
+        });
+        self.windows.last().unwrap()
+    }
+
+    pub fn get_window(&self, id: WindowId) -> Option<&VirtualWindow> {
+        self.windows.iter().find(|w| w.id == id)
+    }
+
+    pub fn get_window_mut(&mut self, id: WindowId) -> Option<&mut VirtualWindow> {
+        self.windows.iter_mut().find(|w| w.id == id)
+    }
+
+    pub fn remove_window(&mut self, id: WindowId) -> Option<VirtualWindow> {
+        Some(
+            self.windows
+                .remove(self.windows.iter().position(|w| w.id == id)?),
+        )
+    }
+
+    pub fn windows(&self) -> &[VirtualWindow] {
+        &self.windows
+    }
+
+    /// Number of completed iterations, incremented after each `AboutToWait`.
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Scripted time of the current event, if the script is stamped.
+    pub fn time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    pub fn exit(&self) {
+        self.exit.set(true);
+    }
+
+    fn take_redraw_requests(&self) -> Vec<WindowId> {
+        self.windows
+            .iter()
+            .filter(|w| w.redraw_requested.take())
+            .map(|w| w.id)
+            .collect()
+    }
+
+    fn dt(&mut self, kind: &HeadlessPlatformEventKind) -> Option<Duration> {
+        let now = self.time?;
+        let last = match kind {
+            HeadlessPlatformEventKind::Init | HeadlessPlatformEventKind::AboutToWait => {
+                &mut self.last_update
+            }
+            HeadlessPlatformEventKind::WindowEvent {
+                window_id,
+                window_event: WindowEvent::RedrawRequested,
+            } => &mut self.get_window_mut(*window_id)?.last_redraw,
+            _ => return None,
+        };
+        Some(now.saturating_sub(last.replace(now).unwrap_or(now)))
+    }
+}
+
+impl AsMut<HeadlessPlatformHandle> for HeadlessPlatformEvent<'_> {
+    fn as_mut(&mut self) -> &mut HeadlessPlatformHandle {
+        self.handle
+    }
+}
+
+impl Platform for HeadlessPlatform {
+    type Event<'a> = HeadlessPlatformEvent<'a>;
+
+    fn run<T: for<'a> nuum_core::Controller<Self::Event<'a>>>(&mut self, controller: &mut T) {
+        let mut handle = HeadlessPlatformHandle::default();
+        let mut script = self.script.iter().cloned();
+
+        let mut dispatch = |handle: &mut HeadlessPlatformHandle, kind| {
+            let dt = handle.dt(&kind);
+            controller.run(HeadlessPlatformEvent {
+                handle,
+                kind,
+                dt,
+                handled: false,
+            });
+        };
+
+        while !handle.exit.get() && self.max_iterations.is_none_or(|n| handle.iteration < n) {
+            let kind = match script.next() {
+                Some(ScriptedEvent { time, kind }) => {
+                    handle.time = time.or(handle.time);
+                    kind
+                }
+                None if self.max_iterations.is_some() => HeadlessPlatformEventKind::AboutToWait,
+                None => break,
+            };
+
+            match &kind {
+                HeadlessPlatformEventKind::WindowEvent {
+                    window_id,
+                    window_event: WindowEvent::Resized { width, height },
+                } => {
+                    if let Some(window) = handle.get_window_mut(*window_id) {
+                        window.size = [*width, *height];
+                    }
+                }
+                HeadlessPlatformEventKind::AboutToWait => {
+                    for window_id in handle.take_redraw_requests() {
+                        dispatch(
+                            &mut handle,
+                            HeadlessPlatformEventKind::WindowEvent {
+                                window_id,
+                                window_event: WindowEvent::RedrawRequested,
+                            },
+                        );
+                    }
+
+                    // Close the application if every window was closed
+                    if handle.windows.is_empty() && handle.next_window_id > 0 {
+                        handle.exit();
+                    }
+                }
+                _ => (),
+            }
+
+            let is_iteration_end = matches!(kind, HeadlessPlatformEventKind::AboutToWait);
+            dispatch(&mut handle, kind);
+
+            if is_iteration_end {
+                handle.iteration += 1;
+            }
+        }
+    }
+}
+
+impl PlatformEvent for HeadlessPlatformEvent<'_> {
+    fn exit(&self) {
+        self.handle.exit();
+    }
+
+    fn is_update(&self) -> bool {
+        matches!(
+            self.kind,
+            HeadlessPlatformEventKind::Init | HeadlessPlatformEventKind::AboutToWait
+        )
+    }
+
+    fn dt(&self) -> Option<Duration> {
+        self.dt
+    }
+}
+
+impl Propagation for HeadlessPlatformEvent<'_> {
+    fn is_handled(&self) -> bool {
+        self.handled
+    }
+
+    fn set_handled(&mut self) {
+        self.handled = true;
+    }
+}
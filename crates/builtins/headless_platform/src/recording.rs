@@ -0,0 +1,54 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ScriptedEvent;
+
+/// Version of the recording format, bumped on every breaking change of the event types.
+pub const RECORDING_VERSION: u32 = 1;
+
+/// Stream of timestamped platform events, that can be replayed with [`crate::HeadlessPlatform::replay`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    pub version: u32,
+    pub events: Vec<ScriptedEvent>,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self {
+            version: RECORDING_VERSION,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl Recording {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+
+        // The version is checked first, the events of another version may not parse
+        let data = fs::read(path)?;
+        let Header { version } = serde_json::from_slice(&data)?;
+        if version != RECORDING_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unsupported recording version {version}, expected {RECORDING_VERSION}"),
+            ));
+        }
+
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        serde_json::to_writer(BufWriter::new(File::create(path)?), self)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use nuum_core::platform::Platform;
+use nuum_headless_platform::{
+    HeadlessPlatform, HeadlessPlatformEvent, HeadlessPlatformEventKind, WindowAttributes,
+    WindowEvent, WindowId,
+};
+
+fn window_event(id: u64, window_event: WindowEvent) -> HeadlessPlatformEventKind {
+    HeadlessPlatformEventKind::WindowEvent {
+        window_id: WindowId(id),
+        window_event,
+    }
+}
+
+fn name(kind: &HeadlessPlatformEventKind) -> String {
+    match kind {
+        HeadlessPlatformEventKind::WindowEvent {
+            window_id,
+            window_event,
+        } => format!("{window_event:?} {}", window_id.0),
+        kind => format!("{kind:?}"),
+    }
+}
+
+/// Runs the platform with a controller opening a window on `Init` and removing it on `CloseRequested`.
+fn run(mut platform: HeadlessPlatform, windows: usize) -> Vec<String> {
+    let mut log = Vec::new();
+    platform.run(&mut |event: HeadlessPlatformEvent| {
+        log.push(name(&event.kind));
+        match &event.kind {
+            HeadlessPlatformEventKind::Init => {
+                for _ in 0..windows {
+                    event.handle.create_window(WindowAttributes::default());
+                }
+            }
+            HeadlessPlatformEventKind::WindowEvent {
+                window_id,
+                window_event: WindowEvent::CloseRequested,
+            } => {
+                event.handle.remove_window(*window_id);
+            }
+            _ => (),
+        }
+    });
+    log
+}
+
+#[test]
+fn redraws_are_dispatched_before_about_to_wait() {
+    let mut platform = HeadlessPlatform::new([
+        HeadlessPlatformEventKind::Init,
+        HeadlessPlatformEventKind::AboutToWait,
+        HeadlessPlatformEventKind::AboutToWait,
+        HeadlessPlatformEventKind::AboutToWait,
+    ]);
+    let mut log = Vec::new();
+    platform.run(&mut |event: HeadlessPlatformEvent| {
+        log.push(name(&event.kind));
+        match &event.kind {
+            HeadlessPlatformEventKind::Init => {
+                event.handle.create_window(WindowAttributes::default());
+                event.handle.create_window(WindowAttributes::default());
+                event.handle.windows()[1].request_redraw();
+            }
+            // Requests made during a redraw are dispatched on the next iteration
+            HeadlessPlatformEventKind::WindowEvent {
+                window_id: WindowId(1),
+                window_event: WindowEvent::RedrawRequested,
+            } if event.handle.iteration() == 0 => {
+                event.handle.windows()[0].request_redraw();
+                event.handle.windows()[1].request_redraw();
+            }
+            _ => (),
+        }
+    });
+
+    assert_eq!(
+        log,
+        [
+            "Init",
+            "RedrawRequested 1",
+            "AboutToWait",
+            "RedrawRequested 0",
+            "RedrawRequested 1",
+            "AboutToWait",
+            "AboutToWait"
+        ]
+    );
+}
+
+#[test]
+fn exits_once_every_window_is_closed() {
+    let platform = HeadlessPlatform::new([
+        HeadlessPlatformEventKind::Init,
+        window_event(0, WindowEvent::CloseRequested),
+        HeadlessPlatformEventKind::AboutToWait,
+        window_event(1, WindowEvent::CloseRequested),
+        HeadlessPlatformEventKind::AboutToWait,
+        HeadlessPlatformEventKind::AboutToWait,
+    ]);
+
+    assert_eq!(
+        run(platform, 2),
+        [
+            "Init",
+            "CloseRequested 0",
+            "AboutToWait",
+            "CloseRequested 1",
+            "AboutToWait"
+        ]
+    );
+}
+
+#[test]
+fn runs_without_windows_until_the_script_ends() {
+    let platform = HeadlessPlatform::new([
+        HeadlessPlatformEventKind::Init,
+        HeadlessPlatformEventKind::AboutToWait,
+        HeadlessPlatformEventKind::AboutToWait,
+    ]);
+
+    assert_eq!(run(platform, 0), ["Init", "AboutToWait", "AboutToWait"]);
+}
+
+#[test]
+fn max_iterations_extends_and_truncates_the_script() {
+    let platform = HeadlessPlatform::new([HeadlessPlatformEventKind::Init]).with_max_iterations(3);
+    assert_eq!(
+        run(platform, 1),
+        ["Init", "AboutToWait", "AboutToWait", "AboutToWait"]
+    );
+
+    let platform = HeadlessPlatform::new([
+        HeadlessPlatformEventKind::Init,
+        HeadlessPlatformEventKind::AboutToWait,
+        window_event(0, WindowEvent::Focused(true)),
+        HeadlessPlatformEventKind::AboutToWait,
+        HeadlessPlatformEventKind::AboutToWait,
+    ])
+    .with_max_iterations(2);
+    assert_eq!(
+        run(platform, 1),
+        ["Init", "AboutToWait", "Focused(true) 0", "AboutToWait"]
+    );
+}
@@ -0,0 +1,44 @@
+use std::{fs, path::PathBuf};
+
+use nuum_headless_platform::{
+    recording::RECORDING_VERSION, HeadlessPlatformEventKind, Recording, ScriptedEvent,
+};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("nuum_{name}_{}.json", std::process::id()))
+}
+
+#[test]
+fn recordings_round_trip() {
+    let path = temp_path("round_trip");
+    let recording = Recording {
+        events: vec![
+            ScriptedEvent::from(HeadlessPlatformEventKind::Init),
+            ScriptedEvent::from(HeadlessPlatformEventKind::AboutToWait),
+        ],
+        ..Recording::default()
+    };
+
+    recording.save(&path).unwrap();
+    let loaded = Recording::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), recording);
+}
+
+#[test]
+fn version_is_checked_before_the_events() {
+    let path = temp_path("version");
+    let version = RECORDING_VERSION + 1;
+    fs::write(
+        &path,
+        format!(r#"{{"version":{version},"events":[{{"unknown":"event"}}]}}"#),
+    )
+    .unwrap();
+
+    let error = Recording::load(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        error.to_string(),
+        format!("Unsupported recording version {version}, expected {RECORDING_VERSION}")
+    );
+}
@@ -0,0 +1,39 @@
+//! Controller library, build it with `cargo build -p nuum_hot_reload --example counter` and load the
+//! `target/debug/examples/libcounter.so` (or `counter.dll`) with a `HotReloadPort<UpdateEvent>`.
+//! Edit and rebuild while the app runs, the count is kept across reloads.
+
+use nuum_core::Controller;
+use nuum_event_loop::UpdateEvent;
+use nuum_hot_reload::Reload;
+
+pub struct Counter {
+    updates: u64,
+}
+
+impl Reload for Counter {
+    fn save(&self) -> Vec<u8> {
+        self.updates.to_le_bytes().to_vec()
+    }
+
+    fn load(state: Option<&[u8]>) -> Self {
+        let updates = state
+            .and_then(|state| state.try_into().ok())
+            .map_or(0, u64::from_le_bytes);
+        Self { updates }
+    }
+}
+
+impl Controller<UpdateEvent> for Counter {
+    fn run(&mut self, event: UpdateEvent) {
+        self.updates += 1;
+        if self.updates.is_multiple_of(60) {
+            println!(
+                "{} updates, {:.1}s",
+                self.updates,
+                event.time.elapsed_secs()
+            );
+        }
+    }
+}
+
+nuum_hot_reload::export_controller!(Counter, UpdateEvent);
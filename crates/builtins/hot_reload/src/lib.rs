@@ -0,0 +1,357 @@
+use std::{
+    cell::RefCell,
+    ffi::c_void,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, SystemTime},
+};
+
+use libloading::{Library, Symbol};
+use nuum_core::{
+    error::{Error, Result},
+    platform::PlatformEvent,
+    Controller, Port,
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use nuum_core::Controller;
+    pub use std::{
+        any::type_name,
+        ffi::c_void,
+        panic::{catch_unwind, AssertUnwindSafe},
+        ptr, slice,
+    };
+}
+
+/// State carried across reloads of a library controller, see [`export_controller!`].
+pub trait Reload: Sized {
+    /// Serializes the state before the library is unloaded.
+    fn save(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// Builds the controller, from the state saved by the previous version of the library if any.
+    fn load(state: Option<&[u8]>) -> Self;
+}
+
+/// Exports a `Controller<Event> + Reload` type from a `cdylib`, to be loaded by a [`HotReloadPort`].
+///
+/// ```ignore
+/// nuum_hot_reload::export_controller!(Gameplay, UpdateEvent);
+/// ```
+///
+/// The library and the app must be built by the same compiler with the same version of the event type, events are
+/// passed as is across the library boundary. Panics of the controller are caught in the library: a panic while
+/// loading fails the reload, a panic while running is logged by the port.
+///
+/// See `examples/counter.rs` for a complete library.
+#[macro_export]
+macro_rules! export_controller {
+    ($controller:ty, $event:ty) => {
+        #[no_mangle]
+        pub unsafe extern "C" fn nuum_hot_event_type(name: *mut &'static str) {
+            *name = $crate::__private::type_name::<$event>();
+        }
+
+        /// Returns null if loading the controller panicked.
+        #[no_mangle]
+        pub unsafe extern "C" fn nuum_hot_create(
+            state: *const u8,
+            len: usize,
+        ) -> *mut $crate::__private::c_void {
+            let state =
+                (!state.is_null()).then(|| $crate::__private::slice::from_raw_parts(state, len));
+            let load = $crate::__private::AssertUnwindSafe(|| {
+                Box::new(<$controller as $crate::Reload>::load(state))
+            });
+            match $crate::__private::catch_unwind(load) {
+                Ok(controller) => Box::into_raw(controller) as *mut $crate::__private::c_void,
+                Err(_) => $crate::__private::ptr::null_mut(),
+            }
+        }
+
+        /// Returns false if the controller panicked.
+        #[no_mangle]
+        pub unsafe extern "C" fn nuum_hot_run(
+            controller: *mut $crate::__private::c_void,
+            event: *mut $crate::__private::c_void,
+        ) -> bool {
+            let controller = &mut *(controller as *mut $controller);
+            let event = $crate::__private::ptr::read(event as *mut $event);
+            $crate::__private::catch_unwind($crate::__private::AssertUnwindSafe(|| {
+                $crate::__private::Controller::run(controller, event)
+            }))
+            .is_ok()
+        }
+
+        /// Returns false if saving the state panicked. The state is freed by the library, after being copied by
+        /// `write` into the `sink` owned by the app.
+        #[no_mangle]
+        pub unsafe extern "C" fn nuum_hot_save(
+            controller: *mut $crate::__private::c_void,
+            sink: *mut $crate::__private::c_void,
+            write: unsafe extern "C" fn(*mut $crate::__private::c_void, *const u8, usize),
+        ) -> bool {
+            let controller = &*(controller as *mut $controller);
+            $crate::__private::catch_unwind($crate::__private::AssertUnwindSafe(|| {
+                let state = $crate::Reload::save(controller);
+                write(sink, state.as_ptr(), state.len());
+            }))
+            .is_ok()
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn nuum_hot_drop(controller: *mut $crate::__private::c_void) {
+            let controller = Box::from_raw(controller as *mut $controller);
+            // A panicking drop leaks the rest of the controller rather than unwinding into the app
+            let _ = $crate::__private::catch_unwind($crate::__private::AssertUnwindSafe(|| {
+                drop(controller)
+            }));
+        }
+    };
+}
+
+type EventTypeFn = unsafe extern "C" fn(*mut &'static str);
+type CreateFn = unsafe extern "C" fn(*const u8, usize) -> *mut c_void;
+type RunFn = unsafe extern "C" fn(*mut c_void, *mut c_void) -> bool;
+type WriteFn = unsafe extern "C" fn(*mut c_void, *const u8, usize);
+type SaveFn = unsafe extern "C" fn(*mut c_void, *mut c_void, WriteFn) -> bool;
+type DropFn = unsafe extern "C" fn(*mut c_void);
+
+struct Loaded {
+    controller: *mut c_void,
+    run: RunFn,
+    save: SaveFn,
+    drop: DropFn,
+    copy: PathBuf,
+    // Dropped last, the functions above point into it
+    _library: Library,
+}
+
+impl Loaded {
+    fn load<E>(path: &Path, copy: PathBuf, state: Option<&[u8]>) -> Result<Self> {
+        // Loads a copy, the original stays writable by the compiler and is never cached by the loader
+        let loaded = std::fs::copy(path, &copy)
+            .map_err(|e| library_error(path, e.into()))
+            .and_then(|_| Self::load_copy::<E>(path, copy.clone(), state));
+        // The library is unloaded on errors, so the copy can be removed on every platform
+        if loaded.is_err() {
+            let _ = std::fs::remove_file(&copy);
+        }
+        loaded
+    }
+
+    fn load_copy<E>(path: &Path, copy: PathBuf, state: Option<&[u8]>) -> Result<Self> {
+        let error = |source: Box<dyn std::error::Error + Send + Sync>| library_error(path, source);
+        let library = unsafe { Library::new(&copy) }.map_err(|e| error(e.into()))?;
+
+        unsafe {
+            let event_type: Symbol<EventTypeFn> = library
+                .get(b"nuum_hot_event_type")
+                .map_err(|e| error(e.into()))?;
+            let mut name = "";
+            event_type(&mut name);
+            if name != std::any::type_name::<E>() {
+                return Err(error(
+                    format!(
+                        "library controller handles {name}, expected {}",
+                        std::any::type_name::<E>()
+                    )
+                    .into(),
+                ));
+            }
+
+            let create = *library
+                .get::<CreateFn>(b"nuum_hot_create")
+                .map_err(|e| error(e.into()))?;
+            let run = *library
+                .get::<RunFn>(b"nuum_hot_run")
+                .map_err(|e| error(e.into()))?;
+            let save = *library
+                .get::<SaveFn>(b"nuum_hot_save")
+                .map_err(|e| error(e.into()))?;
+            let drop = *library
+                .get::<DropFn>(b"nuum_hot_drop")
+                .map_err(|e| error(e.into()))?;
+
+            let controller = match state {
+                Some(state) => create(state.as_ptr(), state.len()),
+                None => create(std::ptr::null(), 0),
+            };
+            if controller.is_null() {
+                return Err(error("library controller panicked while loading".into()));
+            }
+
+            Ok(Self {
+                controller,
+                run,
+                save,
+                drop,
+                copy,
+                _library: library,
+            })
+        }
+    }
+
+    fn save(&self) -> Option<Vec<u8>> {
+        // Copies the state of the library into a vector allocated by the app
+        unsafe extern "C" fn write(sink: *mut c_void, state: *const u8, len: usize) {
+            let sink = &mut *(sink as *mut Vec<u8>);
+            sink.extend_from_slice(std::slice::from_raw_parts(state, len));
+        }
+
+        let mut state = Vec::new();
+        let sink = &mut state as *mut Vec<u8> as *mut c_void;
+        if unsafe { (self.save)(self.controller, sink, write) } {
+            Some(state)
+        } else {
+            tracing::error!(
+                "Library controller panicked while saving its state, reloading without it"
+            );
+            None
+        }
+    }
+}
+
+fn library_error(path: &Path, source: Box<dyn std::error::Error + Send + Sync>) -> Error {
+    Error::Library {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+impl Drop for Loaded {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.controller) };
+        // Removing the copy of a loaded library is fine on unix, windows refuses until it is unloaded
+        let _ = std::fs::remove_file(&self.copy);
+    }
+}
+
+/// Controller implemented by the library of a [`HotReloadPort`], does nothing while no library is loaded.
+pub struct HotController<E> {
+    loaded: Rc<RefCell<Option<Loaded>>>,
+    _marker: PhantomData<fn(E)>,
+}
+
+impl<E> Clone for HotController<E> {
+    fn clone(&self) -> Self {
+        Self {
+            loaded: self.loaded.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E> Controller<E> for HotController<E> {
+    fn run(&mut self, input: E) {
+        if let Some(loaded) = &*self.loaded.borrow() {
+            let mut event = ManuallyDrop::new(input);
+            // Ownership of the event moves to the library
+            if !unsafe { (loaded.run)(loaded.controller, &mut *event as *mut E as *mut c_void) } {
+                tracing::error!("Library controller panicked");
+            }
+        }
+    }
+}
+
+/// Loads a controller exported with [`export_controller!`] from a `cdylib`, reloading it when the file changes.
+///
+/// The library is checked on platform updates, between two `AboutToWait` events, so that swaps happen at a frame
+/// boundary. State is carried across reloads with [`Reload`]. Events reach the library through the
+/// [`HotController`] handle, e.g. a field of the inner controller.
+pub struct HotReloadPort<E> {
+    path: PathBuf,
+    controller: HotController<E>,
+    modified: Option<SystemTime>,
+    version: u32,
+    /// Minimum age of a change before reloading, so that a library still being written is not loaded.
+    pub debounce: Duration,
+}
+
+impl<E> HotReloadPort<E> {
+    /// Loads the library at `path`, e.g. `target/debug/libgameplay.so`.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let mut port = Self {
+            path: path.into(),
+            controller: HotController {
+                loaded: Rc::default(),
+                _marker: PhantomData,
+            },
+            modified: None,
+            version: 0,
+            debounce: Duration::from_millis(300),
+        };
+        port.modified = port.modified_time();
+        port.reload()?;
+        Ok(port)
+    }
+
+    pub fn controller(&self) -> HotController<E> {
+        self.controller.clone()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of times the library was loaded, `1` after the first load.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Swaps in the current library, keeping the previous one if loading fails.
+    pub fn reload(&mut self) -> Result<()> {
+        let mut loaded = self.controller.loaded.borrow_mut();
+        let state = loaded.as_ref().and_then(Loaded::save);
+
+        let copy = std::env::temp_dir().join(format!(
+            "nuum-hot-{}-{}-{}",
+            std::process::id(),
+            self.version + 1,
+            self.path
+                .file_name()
+                .map_or("lib".into(), |n| n.to_string_lossy())
+        ));
+        // Old controller is dropped only once the new library is ready
+        let next = Loaded::load::<E>(&self.path, copy, state.as_deref())?;
+        *loaded = Some(next);
+        self.version += 1;
+        tracing::info!(path = %self.path.display(), version = self.version, "Controller library loaded");
+        Ok(())
+    }
+
+    fn modified_time(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    fn poll(&mut self) {
+        let Some(modified) = self.modified_time() else {
+            return;
+        };
+        let settled = modified.elapsed().is_ok_and(|age| age >= self.debounce);
+        if self.modified != Some(modified) && settled {
+            self.modified = Some(modified);
+            if let Err(e) = self.reload() {
+                tracing::error!("Hot reload failed, keeping the previous controller: {e}");
+            }
+        }
+    }
+}
+
+impl<'a, P: PlatformEvent, E, I> Port<'a, P, I> for HotReloadPort<E> {
+    fn port(&mut self, event: &mut P, _: &mut I) {
+        if event.is_update() {
+            self.poll();
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
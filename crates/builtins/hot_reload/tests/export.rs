@@ -0,0 +1,81 @@
+use std::{ffi::c_void, ptr};
+
+use nuum_core::Controller;
+use nuum_hot_reload::Reload;
+
+struct Sum {
+    total: u32,
+}
+
+impl Reload for Sum {
+    fn save(&self) -> Vec<u8> {
+        self.total.to_le_bytes().to_vec()
+    }
+
+    fn load(state: Option<&[u8]>) -> Self {
+        let total = state.map_or(0, |s| {
+            u32::from_le_bytes(s.try_into().expect("Malformed state"))
+        });
+        Self { total }
+    }
+}
+
+impl Controller<u32> for Sum {
+    fn run(&mut self, n: u32) {
+        assert_ne!(n, 0, "Zero is not summable");
+        self.total += n;
+    }
+}
+
+nuum_hot_reload::export_controller!(Sum, u32);
+
+fn run(controller: *mut c_void, mut event: u32) -> bool {
+    unsafe { nuum_hot_run(controller, &mut event as *mut u32 as *mut c_void) }
+}
+
+fn save(controller: *mut c_void) -> Vec<u8> {
+    unsafe extern "C" fn write(sink: *mut c_void, state: *const u8, len: usize) {
+        (*(sink as *mut Vec<u8>)).extend_from_slice(std::slice::from_raw_parts(state, len));
+    }
+
+    let mut state = Vec::new();
+    let sink = &mut state as *mut Vec<u8> as *mut c_void;
+    assert!(unsafe { nuum_hot_save(controller, sink, write) });
+    state
+}
+
+#[test]
+fn exports_event_type() {
+    let mut name = "";
+    unsafe { nuum_hot_event_type(&mut name) };
+    assert_eq!(name, "u32");
+}
+
+#[test]
+fn state_survives_reload() {
+    let controller = unsafe { nuum_hot_create(ptr::null(), 0) };
+    assert!(!controller.is_null());
+    assert!(run(controller, 2));
+    assert!(run(controller, 3));
+    let state = save(controller);
+    unsafe { nuum_hot_drop(controller) };
+
+    let controller = unsafe { nuum_hot_create(state.as_ptr(), state.len()) };
+    assert!(!controller.is_null());
+    assert_eq!(save(controller), 5u32.to_le_bytes());
+    unsafe { nuum_hot_drop(controller) };
+}
+
+#[test]
+fn panics_are_caught_in_the_library() {
+    let controller = unsafe { nuum_hot_create(ptr::null(), 0) };
+    assert!(!run(controller, 0));
+    // The controller stays usable after a caught panic
+    assert!(run(controller, 1));
+    assert_eq!(save(controller), 1u32.to_le_bytes());
+    unsafe { nuum_hot_drop(controller) };
+
+    let malformed = [0u8];
+    let controller = unsafe { nuum_hot_create(malformed.as_ptr(), malformed.len()) };
+    assert!(controller.is_null());
+}
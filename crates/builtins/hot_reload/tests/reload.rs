@@ -0,0 +1,14 @@
+use nuum_hot_reload::HotReloadPort;
+
+#[test]
+fn copies_are_removed_when_loading_fails() {
+    let name = format!("nuum-not-a-library-{}.so", std::process::id());
+    let path = std::env::temp_dir().join(&name);
+    std::fs::write(&path, b"not a library").unwrap();
+
+    assert!(HotReloadPort::<u32>::new(&path).is_err());
+    let copy = std::env::temp_dir().join(format!("nuum-hot-{}-1-{name}", std::process::id()));
+    assert!(!copy.exists());
+
+    std::fs::remove_file(&path).unwrap();
+}
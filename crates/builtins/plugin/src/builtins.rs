@@ -0,0 +1,119 @@
+use std::any::type_name;
+
+use nuum_core::Controller;
+use nuum_event_loop::{EventLoopPort, UpdateEvent, UpdateMode};
+use nuum_gpu::wgpu::Color;
+use nuum_render_graph::{builtins::SetColorPass, res::ResRegistry};
+use nuum_renderer::{FrameLimit, RedrawHandle, RedrawMode, RenderEvent, RenderPort};
+use nuum_win_platform::{
+    window_manager::{WindowClosed, WindowManagerPort, WindowOpened, WindowSpec},
+    winit::window::WindowAttributes,
+    WinPlatformEvent,
+};
+
+use crate::{Plugin, PluginContext};
+
+/// Opens the windows of a [`WindowManagerPort`], closing all of them ends the app.
+pub struct WindowPlugin(pub WindowManagerPort);
+
+impl Default for WindowPlugin {
+    fn default() -> Self {
+        Self::new(WindowAttributes::default().with_title("Nuum window"))
+    }
+}
+
+impl WindowPlugin {
+    /// Single window named `"main"`.
+    pub fn new(attrs: WindowAttributes) -> Self {
+        Self::with_windows([WindowSpec::new("main", attrs)])
+    }
+
+    pub fn with_windows(specs: impl IntoIterator<Item = WindowSpec>) -> Self {
+        Self(
+            specs
+                .into_iter()
+                .fold(WindowManagerPort::new(), WindowManagerPort::with_window),
+        )
+    }
+}
+
+impl<Inner: Controller<WindowOpened> + Controller<WindowClosed> + 'static> Plugin<Inner>
+    for WindowPlugin
+{
+    fn build(self: Box<Self>, ctx: &mut PluginContext<Inner>) {
+        ctx.add_port(self.0);
+    }
+}
+
+/// Renders every window with the render graph contributed by the plugins. <br/>
+/// Clears the frame in a `"clear"` pass, with the color stored in the `Color` resource.
+pub struct RenderPlugin {
+    pub clear_color: Color,
+    pub redraw_mode: RedrawMode,
+    pub redraw: RedrawHandle,
+    pub frame_limit: Option<FrameLimit>,
+}
+
+impl Default for RenderPlugin {
+    fn default() -> Self {
+        Self {
+            clear_color: Color::BLACK,
+            redraw_mode: RedrawMode::default(),
+            redraw: RedrawHandle::new(),
+            frame_limit: None,
+        }
+    }
+}
+
+impl RenderPlugin {
+    pub fn name() -> &'static str {
+        type_name::<Self>()
+    }
+}
+
+impl<Inner: for<'a> Controller<RenderEvent<'a, ResRegistry>> + 'static> Plugin<Inner>
+    for RenderPlugin
+{
+    fn build(self: Box<Self>, ctx: &mut PluginContext<Inner>) {
+        let RenderPlugin {
+            clear_color,
+            redraw_mode,
+            redraw,
+            frame_limit,
+        } = *self;
+        ctx.add_render_graph(move |ctx| {
+            let view = ctx.alloc.frame_view();
+            let color = ctx.add_resource(Some(clear_color)).read();
+            ctx.add_pass("clear", SetColorPass(view.write(), color));
+        });
+
+        ctx.add_fallible_deferred_port(move |ctx| {
+            let port = RenderPort::<_, WinPlatformEvent<'static>, Inner, _>::try_new_with_native(
+                ctx.take_render_graph(),
+                ctx.take_native_renderers(),
+            )?
+            .with_redraw_mode(redraw_mode)
+            .with_redraw_handle(redraw);
+            Ok(match frame_limit {
+                Some(limit) => port.with_frame_limit(limit),
+                None => port,
+            })
+        });
+    }
+}
+
+/// Runs the update loop, see [`EventLoopPort`].
+#[derive(Default)]
+pub struct UpdatePlugin(pub EventLoopPort);
+
+impl UpdatePlugin {
+    pub fn new(mode: UpdateMode) -> Self {
+        Self(EventLoopPort::new(mode))
+    }
+}
+
+impl<Inner: Controller<UpdateEvent> + 'static> Plugin<Inner> for UpdatePlugin {
+    fn build(self: Box<Self>, ctx: &mut PluginContext<Inner>) {
+        ctx.add_port(self.0);
+    }
+}
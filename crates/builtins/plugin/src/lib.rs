@@ -0,0 +1,238 @@
+use std::any::{type_name, type_name_of_val};
+
+use nuum_core::{
+    dynamic::{BoxedPort, DynPorts},
+    error::{Error, Result},
+    Adapter, Port,
+};
+use nuum_gpu::{surface::GpuSurface, Gpu};
+use nuum_render_graph::{
+    pass::PassEncoder,
+    res::{RenderGraphAlloc, ResHandle, ResRegistry},
+    RenderGraph, RenderGraphBuilder,
+};
+use nuum_renderer::native::NativeRenderer;
+use nuum_win_platform::{WinPlatform, WinPlatformEvent};
+
+pub mod builtins;
+
+pub type PluginPorts<Inner> = DynPorts<WinPlatform, Inner>;
+pub type PluginApp<Inner> = Adapter<PluginPorts<Inner>, Inner>;
+
+pub type BoxedNativeRenderer<Inner> =
+    Box<dyn for<'a> NativeRenderer<ResRegistry, WinPlatformEvent<'a>, Inner>>;
+pub type RenderGraphFn = Box<dyn FnMut(&mut RenderGraphContext)>;
+
+/// Bundle of ports, native renderers and render graph passes.
+pub trait Plugin<Inner> {
+    /// Unique name of the plugin, used to resolve dependencies.
+    fn name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+
+    /// Names of the plugins that must be built before this one.
+    fn dependencies(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn build(self: Box<Self>, ctx: &mut PluginContext<Inner>);
+}
+
+type DeferredPort<Inner> = Box<dyn FnOnce(&mut PluginContext<Inner>) -> Result<PortSlot<Inner>>>;
+
+enum PortSlot<Inner> {
+    Port(&'static str, BoxedPort<WinPlatform, Inner>),
+    Deferred(DeferredPort<Inner>),
+}
+
+/// Contributions of the plugins, ports run in the order they were added.
+pub struct PluginContext<Inner> {
+    slots: Vec<PortSlot<Inner>>,
+    natives: Vec<BoxedNativeRenderer<Inner>>,
+    render_graph: Vec<RenderGraphFn>,
+}
+
+impl<Inner: 'static> PluginContext<Inner> {
+    pub fn add_port(
+        &mut self,
+        port: impl for<'a, 'e> Port<'a, WinPlatformEvent<'e>, Inner> + 'static,
+    ) {
+        self.slots
+            .push(PortSlot::Port(type_name_of_val(&port), Box::new(port)));
+    }
+
+    /// Reserves the place of a port built once every plugin was built, with access to all of their contributions.
+    pub fn add_deferred_port<P: for<'a, 'e> Port<'a, WinPlatformEvent<'e>, Inner> + 'static>(
+        &mut self,
+        builder: impl FnOnce(&mut PluginContext<Inner>) -> P + 'static,
+    ) {
+        self.add_fallible_deferred_port(|ctx| Ok(builder(ctx)));
+    }
+
+    /// Same as [`PluginContext::add_deferred_port`], the error is returned by [`Plugins::try_build`].
+    pub fn add_fallible_deferred_port<
+        P: for<'a, 'e> Port<'a, WinPlatformEvent<'e>, Inner> + 'static,
+    >(
+        &mut self,
+        builder: impl FnOnce(&mut PluginContext<Inner>) -> Result<P> + 'static,
+    ) {
+        self.slots.push(PortSlot::Deferred(Box::new(|ctx| {
+            Ok(PortSlot::Port(type_name::<P>(), Box::new(builder(ctx)?)))
+        })));
+    }
+
+    pub fn add_native_renderer(
+        &mut self,
+        native: impl for<'a> NativeRenderer<ResRegistry, WinPlatformEvent<'a>, Inner> + 'static,
+    ) {
+        self.natives.push(Box::new(native));
+    }
+
+    /// Adds passes and resources to the render graph of every surface.
+    pub fn add_render_graph(&mut self, f: impl FnMut(&mut RenderGraphContext) + 'static) {
+        self.render_graph.push(Box::new(f));
+    }
+
+    pub fn take_native_renderers(&mut self) -> Vec<BoxedNativeRenderer<Inner>> {
+        std::mem::take(&mut self.natives)
+    }
+
+    /// Takes the render graph contributions as a render graph builder, as expected by the `RenderPort`.
+    pub fn take_render_graph(
+        &mut self,
+    ) -> impl FnMut(&Gpu, &GpuSurface<'static>) -> Result<(RenderGraph, ResRegistry)> + 'static
+    {
+        let mut fns = std::mem::take(&mut self.render_graph);
+        move |gpu, surface| {
+            let mut ctx = RenderGraphContext {
+                gpu,
+                surface,
+                alloc: RenderGraphAlloc::default(),
+                resources: ResRegistry::default(),
+                builder: RenderGraph::builder(),
+            };
+            for f in &mut fns {
+                f(&mut ctx);
+            }
+            Ok((ctx.builder.try_build(ctx.alloc)?, ctx.resources))
+        }
+    }
+}
+
+pub struct RenderGraphContext<'a> {
+    pub gpu: &'a Gpu,
+    pub surface: &'a GpuSurface<'static>,
+    pub alloc: RenderGraphAlloc,
+    pub resources: ResRegistry,
+    builder: RenderGraphBuilder,
+}
+
+impl RenderGraphContext<'_> {
+    pub fn add_pass(&mut self, name: impl Into<String>, pass: impl PassEncoder) {
+        self.builder = std::mem::take(&mut self.builder).with_pass(name, pass);
+    }
+
+    /// Allocates a resource and registers its handle, so other plugins can find it by type.
+    pub fn add_resource<T: 'static>(&mut self, value: Option<T>) -> &ResHandle<T> {
+        let handle = self.alloc.push(value);
+        self.resources.insert(handle);
+        self.resources.get().unwrap()
+    }
+}
+
+/// Builds an app from plugins, resolving them in dependency order.
+pub struct Plugins<Inner> {
+    plugins: Vec<Box<dyn Plugin<Inner>>>,
+}
+
+impl<Inner> Default for Plugins<Inner> {
+    fn default() -> Self {
+        Self {
+            plugins: Vec::new(),
+        }
+    }
+}
+
+impl<Inner: 'static> Plugins<Inner> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_plugin(mut self, plugin: impl Plugin<Inner> + 'static) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    /// Adds the plugins of `plugins` after the current ones, keeping their order.
+    pub fn with_plugins(mut self, plugins: Plugins<Inner>) -> Self {
+        self.plugins.extend(plugins.plugins);
+        self
+    }
+
+    /// Panics if the plugins cannot be resolved or fail to build, see [`Plugins::try_build`].
+    pub fn build(self, inner: Inner) -> PluginApp<Inner> {
+        self.try_build(inner).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fails on duplicate plugins, missing or cyclic dependencies, and ports failing to build.
+    pub fn try_build(self, inner: Inner) -> Result<PluginApp<Inner>> {
+        let mut ctx = PluginContext {
+            slots: Vec::new(),
+            natives: Vec::new(),
+            render_graph: Vec::new(),
+        };
+
+        for plugin in resolve(self.plugins)? {
+            plugin.build(&mut ctx);
+        }
+
+        let mut ports = PluginPorts::new();
+        for slot in std::mem::take(&mut ctx.slots) {
+            let slot = match slot {
+                PortSlot::Deferred(builder) => builder(&mut ctx)?,
+                slot => slot,
+            };
+            if let PortSlot::Port(name, port) = slot {
+                ports.insert_named(0, name, port);
+            }
+        }
+
+        Ok(Adapter::new(ports, inner))
+    }
+}
+
+/// Sorts plugins so that dependencies come first, keeping the insertion order otherwise.
+fn resolve<Inner>(mut pending: Vec<Box<dyn Plugin<Inner>>>) -> Result<Vec<Box<dyn Plugin<Inner>>>> {
+    for (i, plugin) in pending.iter().enumerate() {
+        if pending[..i].iter().any(|p| p.name() == plugin.name()) {
+            return Err(Error::DuplicatePlugin {
+                name: plugin.name().to_owned(),
+            });
+        }
+        for dependency in plugin.dependencies() {
+            if !pending.iter().any(|p| p.name() == dependency) {
+                return Err(Error::MissingPluginDependency {
+                    plugin: plugin.name().to_owned(),
+                    dependency: dependency.to_owned(),
+                });
+            }
+        }
+    }
+
+    let mut resolved: Vec<Box<dyn Plugin<Inner>>> = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let index = pending.iter().position(|plugin| {
+            plugin
+                .dependencies()
+                .iter()
+                .all(|d| resolved.iter().any(|p| p.name() == *d))
+        });
+        let Some(index) = index else {
+            return Err(Error::CyclicPlugins {
+                names: pending.iter().map(|p| p.name().to_owned()).collect(),
+            });
+        };
+        resolved.push(pending.remove(index));
+    }
+    Ok(resolved)
+}
@@ -0,0 +1,93 @@
+use std::{cell::RefCell, rc::Rc};
+
+use nuum_core::error::Error;
+use nuum_plugin::{Plugin, PluginContext, Plugins};
+
+type Built = Rc<RefCell<Vec<&'static str>>>;
+
+struct Named {
+    name: &'static str,
+    dependencies: Vec<&'static str>,
+    built: Built,
+}
+
+impl Plugin<()> for Named {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn dependencies(&self) -> Vec<&'static str> {
+        self.dependencies.clone()
+    }
+
+    fn build(self: Box<Self>, _: &mut PluginContext<()>) {
+        self.built.borrow_mut().push(self.name);
+    }
+}
+
+/// Plugins from `(name, dependencies)`, recording their build order.
+fn plugins(specs: &[(&'static str, &[&'static str])]) -> (Plugins<()>, Built) {
+    let built = Built::default();
+    let plugins = specs
+        .iter()
+        .fold(Plugins::new(), |plugins, (name, dependencies)| {
+            plugins.with_plugin(Named {
+                name,
+                dependencies: dependencies.to_vec(),
+                built: built.clone(),
+            })
+        });
+    (plugins, built)
+}
+
+#[test]
+fn dependencies_are_built_first() {
+    let (plugins, built) = plugins(&[
+        ("ui", &["render", "window"]),
+        ("audio", &[]),
+        ("render", &["window"]),
+        ("window", &[]),
+    ]);
+
+    assert!(plugins.try_build(()).is_ok());
+    assert_eq!(*built.borrow(), ["audio", "window", "render", "ui"]);
+}
+
+#[test]
+fn duplicate_plugins_are_rejected() {
+    let (plugins, built) = plugins(&[("window", &[]), ("render", &[]), ("window", &[])]);
+
+    let Err(error) = plugins.try_build(()) else {
+        panic!("Duplicate plugins were built");
+    };
+    assert!(matches!(error, Error::DuplicatePlugin { name } if name == "window"));
+    assert!(built.borrow().is_empty());
+}
+
+#[test]
+fn missing_dependencies_are_rejected() {
+    let (plugins, _) = plugins(&[("render", &["window"])]);
+
+    let Err(error) = plugins.try_build(()) else {
+        panic!("Plugins with a missing dependency were built");
+    };
+    assert_eq!(
+        error.to_string(),
+        "Plugin render depends on window which was not added"
+    );
+    assert!(matches!(
+        error,
+        Error::MissingPluginDependency { plugin, dependency } if plugin == "render" && dependency == "window"
+    ));
+}
+
+#[test]
+fn cycles_are_rejected() {
+    let (plugins, built) = plugins(&[("window", &[]), ("a", &["b"]), ("b", &["a"])]);
+
+    let Err(error) = plugins.try_build(()) else {
+        panic!("Cyclic plugins were built");
+    };
+    assert!(matches!(error, Error::CyclicPlugins { names } if names == ["a", "b"]));
+    assert!(built.borrow().is_empty());
+}
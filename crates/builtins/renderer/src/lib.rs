@@ -1,9 +1,16 @@
-use std::{collections::HashMap, marker::PhantomData, sync::Arc, time::Instant};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use native::NativeRenderer;
 use nuum_core::{
-    event::{Render},
-    Controller, Port,
+    error::Result, event::Render, platform::PlatformEvent, time::Clock, Controller, Port,
 };
 use nuum_gpu::{
     surface::{GpuSurface, SurfaceTarget},
@@ -13,19 +20,85 @@ use nuum_render_graph::{res::ResAccessor, RenderGraph};
 use nuum_win_platform::{
     winit::{
         event::WindowEvent,
+        event_loop::ControlFlow,
         window::{Window, WindowId},
     },
     WinPlatformEvent, WinPlatformEventKind, WinPlatformHandle,
 };
+use tick::FrameTicker;
 
 pub mod native;
+mod pacing;
+mod tick;
+
+pub use pacing::FrameLimit;
 
 pub type SurfaceRenderers<T> = HashMap<WindowId, SurfaceRenderer<T>>;
 
+type RenderGraphBuilderFn<T> =
+    Box<dyn FnMut(&Gpu, &GpuSurface<'static>) -> Result<(RenderGraph, T)>>;
+
+/// Return type of the render graph builders of a [`RenderPort`], the graph and its data or a `Result` of them
+/// when building can fail, e.g. with `RenderGraphBuilder::try_build`.
+pub trait RenderGraphResult<T> {
+    fn into_result(self) -> Result<(RenderGraph, T)>;
+}
+
+impl<T> RenderGraphResult<T> for (RenderGraph, T) {
+    fn into_result(self) -> Result<(RenderGraph, T)> {
+        Ok(self)
+    }
+}
+
+impl<T> RenderGraphResult<T> for Result<(RenderGraph, T)> {
+    fn into_result(self) -> Result<(RenderGraph, T)> {
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedrawMode {
+    /// Redraws every window as soon as its last frame is presented.
+    #[default]
+    Continuous,
+    /// Redraws a window only on its input events, on [`RedrawHandle::invalidate`], or when the native
+    /// renderer asks for it, see [`NativeRenderer::redraw_after`]. <br/>
+    /// The port replaces the initial control flow to wait for the next redraw on `AboutToWait`, without waiting
+    /// longer than the control flow set since by the other ports, see [`WinPlatformHandle::set_control_flow`].
+    Reactive,
+}
+
+/// Requests a redraw of every window in [`RedrawMode::Reactive`], cloneable to be kept by controllers. <br/>
+/// The request is handled on the next `AboutToWait`, wake the event loop with a proxy when invalidating from
+/// another thread.
+#[derive(Debug, Clone, Default)]
+pub struct RedrawHandle(Arc<AtomicBool>);
+
+impl RedrawHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn invalidate(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+}
+
 pub struct RenderPort<T, Platform, Inner, N: NativeRenderer<T, Platform, Inner> = ()> {
     gpu: Gpu,
-    builder: Box<dyn FnMut(&Gpu, &GpuSurface<'static>) -> (RenderGraph, T)>,
+    builder: RenderGraphBuilderFn<T>,
     surfaces: SurfaceRenderers<T>,
+    ticker: FrameTicker,
+    redraw_mode: RedrawMode,
+    redraw: RedrawHandle,
+    frame_limit: Option<FrameLimit>,
+    /// Control flow set by the other ports, and the one scheduled by this port to tell them apart.
+    base_control_flow: ControlFlow,
+    scheduled_control_flow: Option<ControlFlow>,
 
     native: N,
     _marker: PhantomData<(Inner, Platform)>,
@@ -36,44 +109,163 @@ pub struct SurfaceRenderer<T> {
     render_graph: RenderGraph,
     res: T,
     last_frame: Instant,
+    frame_limit: Option<FrameLimit>,
+    frame_start: Option<Instant>,
+    frame_time: Duration,
+    paced_start: Option<Instant>,
+    deferred: bool,
 
     should_close: bool,
 }
 
+impl<T, P, I, N: NativeRenderer<T, P, I>> RenderPort<T, P, I, N> {
+    /// Advances `clock` instead of a private one, ticked once per event loop iteration that redraws a window. <br/>
+    /// The clock must not be advanced by another port, e.g. the clock of an `EventLoopPort`, see [`Clock`].
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.ticker.clock = clock;
+        self
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.ticker.clock
+    }
+
+    pub fn with_redraw_mode(mut self, mode: RedrawMode) -> Self {
+        self.redraw_mode = mode;
+        self
+    }
+
+    /// Invalidated through `redraw` instead of a private handle.
+    pub fn with_redraw_handle(mut self, redraw: RedrawHandle) -> Self {
+        self.redraw = redraw;
+        self
+    }
+
+    pub fn redraw_handle(&self) -> &RedrawHandle {
+        &self.redraw
+    }
+
+    /// Default frame limit of the windows, can be changed per window with [`RenderEventInner::set_frame_limit`].
+    pub fn with_frame_limit(mut self, limit: FrameLimit) -> Self {
+        self.frame_limit = Some(limit);
+        self
+    }
+
+    fn redraw_on_input(&self, window_id: WindowId, handle: &mut WinPlatformHandle) {
+        if self.redraw_mode == RedrawMode::Reactive {
+            if let Some(window) = handle.get_window(window_id) {
+                window.request_redraw();
+            }
+        }
+    }
+
+    /// Requests the due redraws, including frames deferred by the frame limit, and waits until the next one.
+    fn schedule_redraws(&mut self, handle: &mut WinPlatformHandle) {
+        let reactive = self.redraw_mode == RedrawMode::Reactive;
+        let invalidated = reactive && self.redraw.take();
+        let now = Instant::now();
+        let mut next = None::<Instant>;
+        let mut limited = false;
+        for (window_id, surface_renderer) in &mut self.surfaces {
+            limited |= surface_renderer.deferred;
+            let due = if surface_renderer.deferred {
+                Some(surface_renderer.wake_at().unwrap_or(now))
+            } else if !reactive {
+                None
+            } else if invalidated {
+                Some(now)
+            } else {
+                self.native
+                    .redraw_after(*window_id)
+                    .and_then(|delay| surface_renderer.last_frame.checked_add(delay))
+            };
+            match due {
+                Some(due) if due <= now => {
+                    surface_renderer.deferred = false;
+                    if let Some(window) = handle.get_window(*window_id) {
+                        window.request_redraw();
+                    }
+                }
+                Some(due) => next = Some(next.map_or(due, |next| next.min(due))),
+                None => (),
+            }
+        }
+
+        // Keep the control flow of the other ports unless the next redraw is sooner
+        let current = handle.control_flow();
+        match self.scheduled_control_flow {
+            // The initial control flow of the platform is replaced in reactive mode
+            None if reactive => (),
+            Some(scheduled) if scheduled == current => (),
+            _ => self.base_control_flow = current,
+        }
+        let control_flow = next_control_flow(self.base_control_flow, next, limited);
+        handle.set_control_flow(control_flow);
+        self.scheduled_control_flow = Some(control_flow);
+    }
+}
+
+/// Control flow waiting for the `next` redraw, without waiting longer than `base`. <br/>
+/// Polling stops while frames are deferred by a frame limit, so that a capped app does not spin between frames.
+fn next_control_flow(base: ControlFlow, next: Option<Instant>, limited: bool) -> ControlFlow {
+    match (base, next) {
+        (ControlFlow::Poll, Some(next)) if limited => ControlFlow::WaitUntil(next),
+        (ControlFlow::Poll, _) => ControlFlow::Poll,
+        (ControlFlow::WaitUntil(base), Some(next)) => ControlFlow::WaitUntil(base.min(next)),
+        (ControlFlow::Wait, Some(next)) => ControlFlow::WaitUntil(next),
+        (base, None) => base,
+    }
+}
+
 impl<T, P, I> RenderPort<T, P, I> {
-    pub fn new(
-        builder: impl FnMut(&Gpu, &GpuSurface<'static>) -> (RenderGraph, T) + 'static,
+    /// Panics if no graphics device is available, see [`RenderPort::try_new`].
+    pub fn new<R: RenderGraphResult<T>>(
+        builder: impl FnMut(&Gpu, &GpuSurface<'static>) -> R + 'static,
     ) -> Self {
-        let gpu = Gpu::new();
-        let surfaces = HashMap::new();
-        let builder = Box::new(builder);
+        Self::try_new(builder).unwrap_or_else(|e| panic!("{e}"))
+    }
 
-        Self {
-            gpu,
-            builder,
-            surfaces,
-            native: (),
-            _marker: PhantomData,
-        }
+    pub fn try_new<R: RenderGraphResult<T>>(
+        builder: impl FnMut(&Gpu, &GpuSurface<'static>) -> R + 'static,
+    ) -> Result<Self> {
+        Self::try_new_with_native(builder, ())
     }
 }
 
 impl<T, N: NativeRenderer<T, P, I>, P, I> RenderPort<T, P, I, N> {
-    pub fn new_with_native(
-        builder: impl FnMut(&Gpu, &GpuSurface<'static>) -> (RenderGraph, T) + 'static,
+    /// Panics if no graphics device is available, see [`RenderPort::try_new_with_native`].
+    pub fn new_with_native<R: RenderGraphResult<T>>(
+        builder: impl FnMut(&Gpu, &GpuSurface<'static>) -> R + 'static,
         native: N,
     ) -> Self {
-        let gpu = Gpu::new();
+        Self::try_new_with_native(builder, native).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Fails if no graphics device is available. Windows whose surface or render graph cannot be created are
+    /// logged and skipped.
+    pub fn try_new_with_native<R: RenderGraphResult<T>>(
+        mut builder: impl FnMut(&Gpu, &GpuSurface<'static>) -> R + 'static,
+        native: N,
+    ) -> Result<Self> {
+        let gpu = Gpu::try_new()?;
         let surfaces = HashMap::new();
-        let builder = Box::new(builder);
+        let builder = Box::new(move |gpu: &Gpu, surface: &GpuSurface<'static>| {
+            builder(gpu, surface).into_result()
+        });
 
-        Self {
+        Ok(Self {
             gpu,
             builder,
             surfaces,
+            ticker: FrameTicker::new(Clock::new()),
+            redraw_mode: RedrawMode::default(),
+            redraw: RedrawHandle::new(),
+            frame_limit: None,
+            base_control_flow: ControlFlow::default(),
+            scheduled_control_flow: None,
             native,
             _marker: PhantomData,
-        }
+        })
     }
 }
 
@@ -109,6 +301,20 @@ impl<'a, T> RenderEventInner<'a, T> {
     pub fn get_surface(&self) -> &GpuSurface<'static> {
         &self.surface_renderer.surface
     }
+
+    /// Achieved duration between the start of the previous frame of the window and this one.
+    pub fn frame_time(&self) -> Duration {
+        self.surface_renderer.frame_time
+    }
+
+    pub fn frame_limit(&self) -> Option<FrameLimit> {
+        self.surface_renderer.frame_limit
+    }
+
+    /// Limits the frame rate of this window from the next frame, `None` removes the limit.
+    pub fn set_frame_limit(&mut self, limit: Option<FrameLimit>) {
+        self.surface_renderer.frame_limit = limit;
+    }
 }
 
 impl<
@@ -116,10 +322,12 @@ impl<
         'b,
         T,
         Inner: for<'c> Controller<RenderEvent<'c, T>>,
-        N: for<'d> NativeRenderer<T, WinPlatformEvent<'d>, Inner>,
-    > Port<'a, WinPlatformEvent<'b>, Inner> for RenderPort<T, WinPlatformEvent<'_>, Inner, N>
+        U,
+        N: for<'d> NativeRenderer<T, WinPlatformEvent<'d, U>, Inner>,
+    > Port<'a, WinPlatformEvent<'b, U>, Inner>
+    for RenderPort<T, WinPlatformEvent<'_, U>, Inner, N>
 {
-    fn port(&mut self, input: &mut WinPlatformEvent, inner: &mut Inner) {
+    fn port(&mut self, input: &mut WinPlatformEvent<U>, inner: &mut Inner) {
         self.native.on_platform_event(input);
         match &input.kind {
             WinPlatformEventKind::WindowEvent {
@@ -133,36 +341,50 @@ impl<
                         &self.gpu,
                         *window_id,
                         input.handle,
+                        self.frame_limit,
                     ) else {
                         return;
                     };
+                    if !surface_renderer.pace() {
+                        return;
+                    }
+                    surface_renderer.begin_frame();
 
-                    let dt = surface_renderer.last_frame.elapsed();
+                    // Windows redrawn in the same iteration share the tick, from the start of the first frame.
+                    // The platform time step is used when provided, e.g. by `WinPlatform::with_replay`
+                    let start = surface_renderer.frame_start.unwrap_or_else(Instant::now);
+                    let time = self.ticker.tick(start, input.dt());
+                    let _span =
+                        tracing::debug_span!("frame", window = ?window_id, frame = time.frame)
+                            .entered();
 
                     let mut event = Render {
                         inner: RenderEventInner {
                             window_id: *window_id,
                             surface_renderer,
                         },
-                        dt,
+                        time,
                     };
 
-                    self.native.render_port(&mut event, inner);
-                    inner.run(event);
+                    tracing::trace_span!("controllers").in_scope(|| {
+                        self.native.render_port(&mut event, inner);
+                        inner.run(event);
+                    });
 
                     if let Some(frame) = surface_renderer.surface.next_frame(&self.gpu) {
-                        surface_renderer
-                            .render_graph
-                            .run(&self.gpu, frame)
-                            .present(&self.gpu);
+                        let frame = tracing::trace_span!("render_graph")
+                            .in_scope(|| surface_renderer.render_graph.run(&self.gpu, frame));
+                        tracing::trace_span!("present").in_scope(|| frame.present(&self.gpu));
                     }
 
                     surface_renderer.last_frame = Instant::now();
 
-                    if let Some(window) = input.handle.get_window(*window_id) {
-                        window.request_redraw();
-                    } else {
-                        surface_renderer.should_close = true;
+                    match input.handle.get_window(*window_id) {
+                        Some(window) if self.redraw_mode == RedrawMode::Continuous => {
+                            window.request_redraw()
+                        }
+                        Some(_) => (),
+                        None => surface_renderer.should_close = true,
                     }
                 }
                 WindowEvent::Resized(..) | WindowEvent::ScaleFactorChanged { .. } => {
@@ -175,20 +397,48 @@ impl<
                             .into();
                         surface_renderer.surface.resize(&self.gpu, [w, h]);
                     }
+                    self.redraw_on_input(*window_id, input.handle);
                 }
-                _ => (),
+                WindowEvent::Destroyed => (),
+                _ => self.redraw_on_input(*window_id, input.handle),
             },
+            // Surfaces are acquired again lazily on the next redraw
+            WinPlatformEventKind::Suspended => {
+                tracing::debug!("Releasing {} surfaces on suspend", self.surfaces.len());
+                self.surfaces.clear();
+                self.ticker.restart();
+            }
+            // Redraw every window, including reactive ones, so surfaces are acquired again
+            WinPlatformEventKind::Resumed => {
+                for window_id in input.handle.window_ids() {
+                    if let Some(window) = input.handle.get_window(window_id) {
+                        window.request_redraw();
+                    }
+                }
+            }
+            // Ends the frame tick, and pumps wgpu callbacks, e.g. buffer mapping, alongside the async tasks polled
+            // on the same event
+            WinPlatformEventKind::AboutToWait => {
+                self.ticker.end_tick();
+                self.gpu.device.poll(nuum_gpu::wgpu::Maintain::Poll);
+                self.schedule_redraws(input.handle);
+            }
             _ => (),
         }
     }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
 }
 
 fn surface_renderer_lazy<'a, T>(
     surfaces: &'a mut SurfaceRenderers<T>,
-    builder: &mut Box<dyn FnMut(&Gpu, &GpuSurface<'static>) -> (RenderGraph, T)>,
+    builder: &mut RenderGraphBuilderFn<T>,
     gpu: &Gpu,
     window_id: WindowId,
     handle: &mut WinPlatformHandle,
+    frame_limit: Option<FrameLimit>,
 ) -> Option<&'a mut SurfaceRenderer<T>> {
     if let Some(
         __ @ SurfaceRenderer {
@@ -200,20 +450,95 @@ fn surface_renderer_lazy<'a, T>(
         return None;
     }
 
-    Some(surfaces.entry(window_id).or_insert_with(|| {
-            let window_ptr = handle.get_window_ptr::<Arc<Window>>(window_id).expect(
-                "Failed to get window pointer: RenderPort needs Arc<_> window ptr to acquire surface with static lifetime",
+    let entry = match surfaces.entry(window_id) {
+        Entry::Occupied(entry) => return Some(entry.into_mut()),
+        Entry::Vacant(entry) => entry,
+    };
+
+    tracing::info!(window = ?window_id, "Creating surface renderer");
+    let window_ptr = match handle.get_window_ptr::<Arc<Window>>(window_id) {
+        Ok(window_ptr) => window_ptr.clone(),
+        Err(e) => {
+            tracing::error!(
+                window = ?window_id,
+                "RenderPort needs Arc<Window> window pointers to acquire surfaces with a static lifetime: {e}"
             );
-            let surface = gpu
-                .acquire_surface(SurfaceTarget {
-                    size: {
-                        let (w, h) = window_ptr.inner_size().into();
-                        [w, h].into()
-                    },
-                    target: window_ptr.clone().into()
-                });
-                let (render_graph, res) = builder(gpu, &surface);
-
-            SurfaceRenderer {surface, render_graph, res, should_close: false, last_frame: Instant::now()}
-        }))
+            return None;
+        }
+    };
+    let surface = match gpu.try_acquire_surface(SurfaceTarget {
+        size: {
+            let (w, h) = window_ptr.inner_size().into();
+            [w, h].into()
+        },
+        target: window_ptr.into(),
+    }) {
+        Ok(surface) => surface,
+        Err(e) => {
+            tracing::error!(window = ?window_id, "Skipping window: {e}");
+            return None;
+        }
+    };
+    let (render_graph, res) = match builder(gpu, &surface) {
+        Ok(built) => built,
+        Err(e) => {
+            tracing::error!(window = ?window_id, "Skipping window: {e}");
+            return None;
+        }
+    };
+
+    Some(entry.insert(SurfaceRenderer {
+        surface,
+        render_graph,
+        res,
+        should_close: false,
+        last_frame: Instant::now(),
+        frame_limit,
+        frame_start: None,
+        frame_time: Duration::ZERO,
+        paced_start: None,
+        deferred: false,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_redraws_waits_for_limited_frames_when_polling() {
+        let next = Instant::now() + Duration::from_millis(10);
+        assert_eq!(
+            next_control_flow(ControlFlow::Poll, Some(next), true),
+            ControlFlow::WaitUntil(next)
+        );
+
+        // Reactive redraws alone keep polling
+        assert_eq!(
+            next_control_flow(ControlFlow::Poll, Some(next), false),
+            ControlFlow::Poll
+        );
+        assert_eq!(
+            next_control_flow(ControlFlow::Poll, None, false),
+            ControlFlow::Poll
+        );
+    }
+
+    #[test]
+    fn schedule_redraws_keeps_sooner_control_flows() {
+        let next = Instant::now() + Duration::from_millis(10);
+        let sooner = next - Duration::from_millis(5);
+        assert_eq!(
+            next_control_flow(ControlFlow::Wait, Some(next), true),
+            ControlFlow::WaitUntil(next)
+        );
+        assert_eq!(
+            next_control_flow(ControlFlow::WaitUntil(sooner), Some(next), true),
+            ControlFlow::WaitUntil(sooner)
+        );
+        assert_eq!(
+            next_control_flow(ControlFlow::Wait, None, false),
+            ControlFlow::Wait
+        );
+    }
 }
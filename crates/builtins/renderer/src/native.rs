@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use nuum_win_platform::winit::window::WindowId;
 
 use crate::RenderEvent;
 
@@ -5,6 +8,42 @@ use crate::RenderEvent;
 pub trait NativeRenderer<T, P, Inner> {
     fn on_platform_event(&mut self, input: &mut P);
     fn render_port(&mut self, event: &mut RenderEvent<T>, inner: &mut Inner);
+
+    /// Delay after the last frame of the window before it needs to be redrawn, only used in
+    /// [`RedrawMode::Reactive`](crate::RedrawMode::Reactive). `None` waits for input or an invalidation.
+    fn redraw_after(&self, _window_id: WindowId) -> Option<Duration> {
+        None
+    }
+}
+
+impl<T, P, Inner, N: NativeRenderer<T, P, Inner> + ?Sized> NativeRenderer<T, P, Inner> for Box<N> {
+    fn on_platform_event(&mut self, input: &mut P) {
+        N::on_platform_event(self, input);
+    }
+    fn render_port(&mut self, event: &mut RenderEvent<T>, inner: &mut Inner) {
+        N::render_port(self, event, inner);
+    }
+    fn redraw_after(&self, window_id: WindowId) -> Option<Duration> {
+        N::redraw_after(self, window_id)
+    }
+}
+
+impl<T, P, Inner, N: NativeRenderer<T, P, Inner>> NativeRenderer<T, P, Inner> for Vec<N> {
+    fn on_platform_event(&mut self, input: &mut P) {
+        for native in self {
+            native.on_platform_event(input);
+        }
+    }
+    fn render_port(&mut self, event: &mut RenderEvent<T>, inner: &mut Inner) {
+        for native in self {
+            native.render_port(event, inner);
+        }
+    }
+    fn redraw_after(&self, window_id: WindowId) -> Option<Duration> {
+        self.iter()
+            .filter_map(|native| native.redraw_after(window_id))
+            .min()
+    }
 }
 
 macro_rules! impl_for_tuples {
@@ -27,6 +66,10 @@ macro_rules! impl_for_tuples {
                  let ($($t,)*) = self;
                 $( $t.render_port(_event, _inner); )*
             }
+            fn redraw_after(&self, _window_id: WindowId) -> Option<Duration> {
+                let ($($t,)*) = self;
+                None.into_iter()$( .chain($t.redraw_after(_window_id)) )*.min()
+            }
         }
     };
 }
@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use crate::SurfaceRenderer;
+
+/// Minimum duration between the start of two frames of a window, see [`RenderPort::with_frame_limit`](crate::RenderPort::with_frame_limit). <br/>
+/// The event loop sleeps until `spin` before the frame, then spins until its start for a low jitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameLimit {
+    pub frame_time: Duration,
+    pub spin: Duration,
+}
+
+impl FrameLimit {
+    pub fn new(frame_time: Duration) -> Self {
+        Self {
+            frame_time,
+            spin: Duration::from_millis(1),
+        }
+    }
+
+    pub fn fps(fps: f64) -> Self {
+        assert!(
+            fps.is_finite() && fps > 0.,
+            "Target fps must be finite and greater than zero, got {fps}"
+        );
+        Self::new(Duration::from_secs_f64(fps.recip()))
+    }
+}
+
+impl<T> SurfaceRenderer<T> {
+    /// Earliest start of the next frame allowed by the frame limit.
+    pub(crate) fn next_frame_at(&self) -> Option<Instant> {
+        self.paced_start?.checked_add(self.frame_limit?.frame_time)
+    }
+
+    /// When to wake up the event loop for a deferred frame, leaving time to spin.
+    pub(crate) fn wake_at(&self) -> Option<Instant> {
+        let next = self.next_frame_at()?;
+        Some(next.checked_sub(self.frame_limit?.spin).unwrap_or(next))
+    }
+
+    /// Waits for the start of the next frame, returns false and defers the frame if it is too early to spin.
+    pub(crate) fn pace(&mut self) -> bool {
+        if let Some(wake) = self.wake_at() {
+            if Instant::now() < wake {
+                self.deferred = true;
+                return false;
+            }
+        }
+        if let Some(next) = self.next_frame_at() {
+            while Instant::now() < next {
+                std::hint::spin_loop();
+            }
+        }
+        self.deferred = false;
+        true
+    }
+
+    /// Measures the achieved frame time and anchors the pacing of the next frame.
+    pub(crate) fn begin_frame(&mut self) {
+        let start = Instant::now();
+        self.frame_time = self.frame_start.map_or(Duration::ZERO, |last| start - last);
+        self.frame_start = Some(start);
+
+        // Keep a steady cadence from the scheduled start, unless a whole frame was missed
+        self.paced_start = match (self.next_frame_at(), self.frame_limit) {
+            (Some(next), Some(limit))
+                if start.saturating_duration_since(next) < limit.frame_time =>
+            {
+                Some(next)
+            }
+            _ => Some(start),
+        };
+    }
+}
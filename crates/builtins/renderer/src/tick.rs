@@ -0,0 +1,129 @@
+use std::time::{Duration, Instant};
+
+use nuum_core::time::{Clock, Time};
+
+/// Advances a [`Clock`] once per frame tick, shared by the windows redrawn in the same event loop iteration.
+pub(crate) struct FrameTicker {
+    pub(crate) clock: Clock,
+    /// Start of the previous tick, and the time of the current one once a window is redrawn.
+    last_start: Option<Instant>,
+    tick: Option<Time>,
+}
+
+impl FrameTicker {
+    pub(crate) fn new(clock: Clock) -> Self {
+        Self {
+            clock,
+            last_start: None,
+            tick: None,
+        }
+    }
+
+    /// Time of the current tick, started at `start` by the first redrawn window. <br/>
+    /// The clock advances by the platform time step when provided, e.g. by a replay, or by the time since the
+    /// start of the previous tick.
+    pub(crate) fn tick(&mut self, start: Instant, platform_dt: Option<Duration>) -> Time {
+        *self.tick.get_or_insert_with(|| {
+            let measured = self.last_start.replace(start).map(|last| start - last);
+            self.clock
+                .advance(platform_dt.or(measured).unwrap_or_default())
+        })
+    }
+
+    pub(crate) fn end_tick(&mut self) {
+        self.tick = None;
+    }
+
+    /// Measures the next tick from its own start, e.g. after a suspend.
+    pub(crate) fn restart(&mut self) {
+        self.last_start = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nuum_core::platform::{Platform, PlatformEvent};
+    use nuum_headless_platform::{
+        HeadlessPlatform, HeadlessPlatformEvent, HeadlessPlatformEventKind, Recording,
+        ScriptedEvent, WindowAttributes, WindowEvent,
+    };
+
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn replayed_time_steps_drive_the_clock() {
+        let at = |time, kind| ScriptedEvent {
+            time: Some(ms(time)),
+            kind,
+        };
+        let recording = Recording {
+            events: vec![
+                at(0, HeadlessPlatformEventKind::Init),
+                at(16, HeadlessPlatformEventKind::AboutToWait),
+                at(33, HeadlessPlatformEventKind::AboutToWait),
+                at(50, HeadlessPlatformEventKind::AboutToWait),
+            ],
+            ..Recording::default()
+        };
+
+        // Two windows redrawn on every iteration, as by a continuous RenderPort
+        let mut ticker = FrameTicker::new(Clock::new());
+        let mut ticks = Vec::new();
+        HeadlessPlatform::replay(recording).run(&mut |event: HeadlessPlatformEvent| {
+            match &event.kind {
+                HeadlessPlatformEventKind::Init => {
+                    event.handle.create_window(WindowAttributes::default());
+                    event.handle.create_window(WindowAttributes::default());
+                }
+                HeadlessPlatformEventKind::WindowEvent {
+                    window_id,
+                    window_event: WindowEvent::RedrawRequested,
+                } => {
+                    let time = ticker.tick(Instant::now(), event.dt());
+                    ticks.push((window_id.0, time.frame, time.delta));
+                }
+                HeadlessPlatformEventKind::AboutToWait => ticker.end_tick(),
+                _ => (),
+            }
+            if event.is_update() {
+                event
+                    .handle
+                    .windows()
+                    .iter()
+                    .for_each(|w| w.request_redraw());
+            }
+        });
+
+        assert_eq!(
+            ticks,
+            [
+                (0, 1, ms(0)),
+                (1, 1, ms(0)),
+                (0, 2, ms(17)),
+                (1, 2, ms(17)),
+                (0, 3, ms(17)),
+                (1, 3, ms(17))
+            ]
+        );
+        assert_eq!(ticker.clock.time().elapsed, ms(34));
+    }
+
+    #[test]
+    fn ticks_are_measured_without_platform_time_steps() {
+        let mut ticker = FrameTicker::new(Clock::new());
+        let start = Instant::now();
+
+        assert_eq!(ticker.tick(start, None).delta, ms(0));
+        ticker.end_tick();
+        assert_eq!(ticker.tick(start + ms(10), None).delta, ms(10));
+        assert_eq!(ticker.tick(start + ms(12), None).delta, ms(10));
+        ticker.end_tick();
+
+        ticker.restart();
+        assert_eq!(ticker.tick(start + ms(100), None).delta, ms(0));
+    }
+}
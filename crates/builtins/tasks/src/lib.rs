@@ -0,0 +1,182 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use nuum_core::{platform::PlatformEvent, Controller, Port};
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+type WakeFn = Box<dyn Fn() + Send + Sync>;
+
+/// Queues futures on a [`TaskPort`], cheap to clone and usable from any thread.
+pub struct Spawner<T> {
+    queue: Arc<Mutex<Vec<BoxFuture<T>>>>,
+    hook: Arc<WakeHook>,
+}
+
+impl<T> Clone for Spawner<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+            hook: self.hook.clone(),
+        }
+    }
+}
+
+impl<T> Spawner<T> {
+    /// The future is first polled on the next update, its output is sent as an event to the inner controller.
+    pub fn spawn(&self, future: impl Future<Output = T> + Send + 'static) {
+        self.queue
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Box::pin(future));
+        self.hook.wake();
+    }
+
+    /// Replaces the function called when a future is spawned or woken, see [`TaskPort::with_wake`].
+    pub fn set_wake(&self, wake: impl Fn() + Send + Sync + 'static) {
+        *self.hook.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(wake));
+    }
+}
+
+/// Single threaded executor polled on platform update events, e.g. `AboutToWait`. <br/>
+/// Outputs of finished futures are run on the inner controller, use an enum as `T` for several kinds of tasks.
+///
+/// Futures spawned or woken from other threads are polled on the next update. A platform waiting for events has
+/// to be woken up for it, see [`TaskPort::with_wake`].
+pub struct TaskPort<T> {
+    spawner: Spawner<T>,
+    tasks: Vec<Task<T>>,
+}
+
+struct Task<T> {
+    future: BoxFuture<T>,
+    flag: Arc<WakeFlag>,
+}
+
+#[derive(Default)]
+struct WakeHook(Mutex<Option<WakeFn>>);
+
+impl WakeHook {
+    fn wake(&self) {
+        if let Some(wake) = &*self.0.lock().unwrap_or_else(|e| e.into_inner()) {
+            wake();
+        }
+    }
+}
+
+struct WakeFlag {
+    woken: AtomicBool,
+    hook: Arc<WakeHook>,
+}
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        self.hook.wake();
+    }
+}
+
+impl<T> Default for TaskPort<T> {
+    fn default() -> Self {
+        Self {
+            spawner: Spawner {
+                queue: Arc::default(),
+                hook: Arc::default(),
+            },
+            tasks: Vec::new(),
+        }
+    }
+}
+
+impl<T> TaskPort<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `wake` when a future is spawned or woken, from any thread, e.g. to send a user event through an
+    /// `EventLoopProxy` so that a waiting event loop polls the tasks:
+    ///
+    /// ```ignore
+    /// // On `WinPlatformEventKind::Init`
+    /// let proxy = event.proxy();
+    /// let tasks = TaskPort::new().with_wake(move || {
+    ///     let _ = proxy.send_event(());
+    /// });
+    /// ```
+    pub fn with_wake(self, wake: impl Fn() + Send + Sync + 'static) -> Self {
+        self.spawner.set_wake(wake);
+        self
+    }
+
+    pub fn spawner(&self) -> Spawner<T> {
+        self.spawner.clone()
+    }
+
+    pub fn spawn(&self, future: impl Future<Output = T> + Send + 'static) {
+        self.spawner.spawn(future);
+    }
+
+    /// Number of spawned futures not finished yet.
+    pub fn pending(&self) -> usize {
+        let queued = self
+            .spawner
+            .queue
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len();
+        self.tasks.len() + queued
+    }
+
+    /// Polls woken futures once, running the outputs of finished ones on `inner`.
+    pub fn poll(&mut self, inner: &mut impl Controller<T>) {
+        let spawned =
+            std::mem::take(&mut *self.spawner.queue.lock().unwrap_or_else(|e| e.into_inner()));
+        self.tasks.extend(spawned.into_iter().map(|future| Task {
+            future,
+            flag: Arc::new(WakeFlag {
+                woken: AtomicBool::new(true),
+                hook: self.spawner.hook.clone(),
+            }),
+        }));
+
+        let mut i = 0;
+        while i < self.tasks.len() {
+            let task = &mut self.tasks[i];
+            if !task.flag.woken.swap(false, Ordering::Acquire) {
+                i += 1;
+                continue;
+            }
+
+            let waker = Waker::from(task.flag.clone());
+            match task.future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                Poll::Ready(output) => {
+                    self.tasks.swap_remove(i);
+                    inner.run(output);
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+    }
+}
+
+impl<'a, E: PlatformEvent, T, I: Controller<T>> Port<'a, E, I> for TaskPort<T> {
+    fn port(&mut self, event: &mut E, inner: &mut I) {
+        if event.is_update() {
+            self.poll(inner);
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
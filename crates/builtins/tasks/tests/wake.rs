@@ -0,0 +1,87 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{mpsc, Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+use nuum_core::Controller;
+use nuum_tasks::TaskPort;
+
+#[derive(Default)]
+struct Outputs(Vec<u32>);
+
+impl Controller<u32> for Outputs {
+    fn run(&mut self, output: u32) {
+        self.0.push(output);
+    }
+}
+
+/// Resolves once its value is set, from any thread.
+#[derive(Clone, Default)]
+struct Signal(Arc<Mutex<(Option<u32>, Option<Waker>)>>);
+
+impl Signal {
+    fn set(&self, value: u32) {
+        let mut state = self.0.lock().unwrap();
+        state.0 = Some(value);
+        if let Some(waker) = state.1.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Future for Signal {
+    type Output = u32;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+        let mut state = self.0.lock().unwrap();
+        match state.0 {
+            Some(value) => Poll::Ready(value),
+            None => {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[test]
+fn wake_is_called_from_other_threads() {
+    let (sender, receiver) = mpsc::channel();
+    let sender = Mutex::new(sender);
+    let mut tasks = TaskPort::new().with_wake(move || {
+        let _ = sender.lock().unwrap().send(());
+    });
+    let mut outputs = Outputs::default();
+
+    let signal = Signal::default();
+    tasks.spawn(signal.clone());
+    receiver.try_recv().expect("spawning wakes");
+    tasks.poll(&mut outputs);
+    assert_eq!(tasks.pending(), 1);
+    assert!(receiver.try_recv().is_err());
+
+    thread::spawn(move || signal.set(7));
+    receiver
+        .recv_timeout(Duration::from_secs(5))
+        .expect("waking from another thread wakes");
+    tasks.poll(&mut outputs);
+    assert_eq!(outputs.0, [7]);
+    assert_eq!(tasks.pending(), 0);
+}
+
+#[test]
+fn pending_counts_queued_and_running_tasks() {
+    let mut tasks = TaskPort::new();
+    let mut outputs = Outputs::default();
+    tasks.spawn(async { 1 });
+    tasks.spawn(Signal::default());
+    assert_eq!(tasks.pending(), 2);
+
+    tasks.poll(&mut outputs);
+    assert_eq!(outputs.0, [1]);
+    assert_eq!(tasks.pending(), 1);
+}
@@ -0,0 +1,58 @@
+use std::{sync::Arc, thread, time::Duration};
+
+use nuum_core::platform::Platform;
+use nuum_win_platform::{
+    winit::window::{Window, WindowAttributes},
+    TypedWindowId, WinPlatform, WinPlatformEvent, WinPlatformEventKind,
+};
+
+enum UserEvent {
+    Tick(u32),
+}
+
+fn main() {
+    WinPlatform::<UserEvent>::with_user_event().run(&mut AppController { window: None });
+}
+
+struct AppController {
+    window: Option<TypedWindowId<Arc<Window>>>,
+}
+
+impl<'a> nuum_core::Controller<WinPlatformEvent<'a, UserEvent>> for AppController {
+    fn run(&mut self, input: WinPlatformEvent<'a, UserEvent>) {
+        match input.kind {
+            WinPlatformEventKind::Init => {
+                // The proxy wakes the event loop, even when it is waiting for events
+                let proxy = input.proxy();
+                thread::spawn(move || {
+                    for i in 0.. {
+                        thread::sleep(Duration::from_secs(1));
+                        if proxy.send_event(UserEvent::Tick(i)).is_err() {
+                            break;
+                        }
+                    }
+                });
+
+                self.window = input
+                    .handle
+                    .try_create_typed_window(
+                        Arc::new,
+                        WindowAttributes::default().with_title("nuum user events"),
+                    )
+                    .ok();
+            }
+            WinPlatformEventKind::User(UserEvent::Tick(i)) => {
+                if let Some(window) = self.window.and_then(|id| input.handle.get(id)) {
+                    window.set_title(&format!("nuum user events, tick {i}"));
+                }
+            }
+            WinPlatformEventKind::WindowEvent {
+                window_id,
+                window_event: winit::event::WindowEvent::CloseRequested,
+            } => {
+                input.handle.remove_window(window_id);
+            }
+            _ => (),
+        };
+    }
+}
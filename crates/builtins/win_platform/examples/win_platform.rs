@@ -6,7 +6,7 @@ use nuum_win_platform::{
 };
 
 fn main() {
-    WinPlatform::default().run(&mut AppController);
+    WinPlatform::new().run(&mut AppController);
 }
 
 struct AppController;
@@ -0,0 +1,57 @@
+use nuum_core::{platform::Platform, Adapter, Controller};
+use nuum_win_platform::{
+    window_manager::{WindowClosed, WindowManagerPort, WindowOpened, WindowSpec, Windows},
+    winit::window::WindowAttributes,
+    WinPlatform,
+};
+
+fn main() {
+    let port = WindowManagerPort::new()
+        .with_window(WindowSpec::new(
+            "viewport",
+            WindowAttributes::default().with_title("Viewport"),
+        ))
+        .with_window(
+            WindowSpec::new(
+                "inspector",
+                WindowAttributes::default().with_title("Inspector"),
+            )
+            .with_parent("viewport"),
+        );
+    let windows = port.windows();
+
+    let mut app = Adapter::new(port, Tools { windows });
+    WinPlatform::new().run(&mut app);
+}
+
+struct Tools {
+    windows: Windows,
+}
+
+impl Controller<WindowOpened> for Tools {
+    fn run(&mut self, event: WindowOpened) {
+        println!("Opened {} ({:?})", event.name, event.id);
+
+        // Windows can also be declared at runtime
+        if event.name == "inspector" {
+            self.windows.open(
+                WindowSpec::new(
+                    "assets",
+                    WindowAttributes::default().with_title("Asset browser"),
+                )
+                .with_parent("viewport"),
+            );
+        }
+    }
+}
+
+impl Controller<WindowClosed> for Tools {
+    fn run(&mut self, event: WindowClosed) {
+        println!(
+            "Closed {} ({:?}), open: {:?}",
+            event.name,
+            event.id,
+            self.windows.names()
+        );
+    }
+}
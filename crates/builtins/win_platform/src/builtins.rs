@@ -4,6 +4,8 @@ use nuum_core::Port;
 use winit::{event::WindowEvent, window::WindowAttributes};
 
 use crate::{WinPlatformEvent, WinPlatformEventKind};
+
+/// Opens one window on `Init` and removes it when closed, see [`crate::window_manager::WindowManagerPort`] for several windows.
 pub struct SingleWindowPort(pub WindowAttributes);
 
 impl Default for SingleWindowPort {
@@ -12,8 +14,8 @@ impl Default for SingleWindowPort {
     }
 }
 
-impl<'a, 'b, Inner> Port<'a, WinPlatformEvent<'b>, Inner> for SingleWindowPort {
-    fn port(&mut self, event: &mut WinPlatformEvent, _: &mut Inner) {
+impl<'a, 'b, T, Inner> Port<'a, WinPlatformEvent<'b, T>, Inner> for SingleWindowPort {
+    fn port(&mut self, event: &mut WinPlatformEvent<T>, _: &mut Inner) {
         match &event.kind {
             WinPlatformEventKind::Init => {
                 event
@@ -33,4 +35,8 @@ impl<'a, 'b, Inner> Port<'a, WinPlatformEvent<'b>, Inner> for SingleWindowPort {
             _ => (),
         }
     }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
 }
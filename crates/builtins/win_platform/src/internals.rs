@@ -1,59 +1,159 @@
-use nuum_core::Controller;
+use std::{any::type_name, collections::HashMap};
+
+use nuum_core::{
+    error::{Error, Result},
+    Controller,
+};
 use winit::{
-    application::ApplicationHandler, event::WindowEvent, event_loop::ActiveEventLoop,
+    application::ApplicationHandler,
+    event::{StartCause, WindowEvent},
+    event_loop::{ActiveEventLoop, EventLoopProxy},
     window::WindowId,
 };
 
 use crate::{WinPlatformEvent, WinPlatformEventKind, WinPlatformHandle, WindowPtr};
 
-pub type WindowVec = Vec<Box<dyn WindowPtr>>;
+pub type WindowMap = HashMap<WindowId, WindowEntry>;
 
-pub struct WinPlatformRunner<'a, T: for<'b> Controller<WinPlatformEvent<'b>>> {
-    controller: &'a mut T,
-    pub(super) windows: WindowVec,
+pub struct WindowEntry {
+    pub ptr: Box<dyn WindowPtr>,
+    /// Type name of the pointer, for lookup errors.
+    pub type_name: &'static str,
+    /// Creation index, see [`WinPlatformHandle::window_index`].
+    pub index: u64,
 }
 
-impl<'a, T: for<'b> Controller<WinPlatformEvent<'b>>> WinPlatformRunner<'a, T> {
-    pub fn new(controller: &'a mut T) -> Self {
+/// Pointer of the window, if it was created with the pointer type `T`.
+pub fn get_window_ptr<T: WindowPtr>(windows: &WindowMap, id: WindowId) -> Result<&T> {
+    let entry = windows.get(&id).ok_or_else(|| Error::UnknownWindow {
+        id: format!("{id:?}"),
+    })?;
+    entry
+        .ptr
+        .as_any_ref()
+        .downcast_ref::<T>()
+        .ok_or_else(|| Error::WindowPtrType {
+            expected: type_name::<T>().to_owned(),
+            found: entry.type_name.to_owned(),
+        })
+}
+
+pub struct WinPlatformRunner<'a, C: for<'b> Controller<WinPlatformEvent<'b, T>>, T: 'static> {
+    controller: &'a mut C,
+    pub(super) windows: WindowMap,
+    created_windows: u64,
+    proxy: EventLoopProxy<T>,
+    initialized: bool,
+    #[cfg(feature = "record")]
+    pub(super) replay: Option<crate::record::Replay>,
+}
+
+impl<'a, C: for<'b> Controller<WinPlatformEvent<'b, T>>, T: 'static> WinPlatformRunner<'a, C, T> {
+    pub fn new(controller: &'a mut C, proxy: EventLoopProxy<T>) -> Self {
         Self {
             controller,
-            windows: Vec::new(),
+            windows: HashMap::new(),
+            created_windows: 0,
+            proxy,
+            initialized: false,
+            #[cfg(feature = "record")]
+            replay: None,
         }
     }
-}
 
-impl<'a, T: for<'b> Controller<WinPlatformEvent<'b>>> ApplicationHandler
-    for WinPlatformRunner<'a, T>
-{
-    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+    fn dispatch(&mut self, event_loop: &ActiveEventLoop, kind: WinPlatformEventKind<T>) {
+        #[cfg(feature = "record")]
+        let dt = self.replay.as_mut().and_then(|replay| replay.dt(&kind));
+        #[cfg(not(feature = "record"))]
+        let dt = None;
+
         let mut handle = WinPlatformHandle {
             event_loop,
             windows: &mut self.windows,
+            created_windows: &mut self.created_windows,
         };
 
         self.controller.run(WinPlatformEvent {
             handle: &mut handle,
-            kind: WinPlatformEventKind::Init,
+            handled: false,
+            kind,
+            proxy: &self.proxy,
+            dt,
         });
     }
+
+    /// Dispatches the recorded events of the next iteration, and exits once the recording is over.
+    #[cfg(feature = "record")]
+    fn replay_iteration(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        let Some(events) = replay.next_iteration() else {
+            tracing::info!("Replay finished, exiting");
+            event_loop.exit();
+            return;
+        };
+        let events: Vec<_> = events
+            .into_iter()
+            .filter_map(|kind| replay.event(kind, &self.windows))
+            .collect();
+        for kind in events {
+            self.dispatch(event_loop, kind);
+        }
+    }
+}
+
+impl<'a, C: for<'b> Controller<WinPlatformEvent<'b, T>>, T: 'static> ApplicationHandler<T>
+    for WinPlatformRunner<'a, C, T>
+{
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        self.dispatch(event_loop, WinPlatformEventKind::NewEvents(cause));
+    }
+
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        // Resumed can fire several times, only the first one initializes the application
+        let kind = if std::mem::replace(&mut self.initialized, true) {
+            WinPlatformEventKind::Resumed
+        } else {
+            WinPlatformEventKind::Init
+        };
+        self.dispatch(event_loop, kind);
+    }
+
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: T) {
+        self.dispatch(event_loop, WinPlatformEventKind::User(event));
+    }
+
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch(event_loop, WinPlatformEventKind::Suspended);
+    }
+
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch(event_loop, WinPlatformEventKind::Exiting);
+    }
+
+    fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch(event_loop, WinPlatformEventKind::MemoryWarning);
+    }
+
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
         window_id: WindowId,
         window_event: WindowEvent,
     ) {
-        let mut handle = WinPlatformHandle {
+        // Live input is replaced by the recorded one when replaying
+        #[cfg(feature = "record")]
+        if self.replay.is_some() && !crate::record::is_live(&window_event) {
+            return;
+        }
+        self.dispatch(
             event_loop,
-            windows: &mut self.windows,
-        };
-
-        self.controller.run(WinPlatformEvent {
-            handle: &mut handle,
-            kind: WinPlatformEventKind::WindowEvent {
+            WinPlatformEventKind::WindowEvent {
                 window_id,
                 window_event,
             },
-        });
+        );
     }
 
     fn device_event(
@@ -62,34 +162,93 @@ impl<'a, T: for<'b> Controller<WinPlatformEvent<'b>>> ApplicationHandler
         device_id: winit::event::DeviceId,
         device_event: winit::event::DeviceEvent,
     ) {
-        let mut handle = WinPlatformHandle {
+        #[cfg(feature = "record")]
+        if self.replay.is_some() {
+            return;
+        }
+        self.dispatch(
             event_loop,
-            windows: &mut self.windows,
-        };
-
-        self.controller.run(WinPlatformEvent {
-            handle: &mut handle,
-            kind: WinPlatformEventKind::DeviceEvent {
+            WinPlatformEventKind::DeviceEvent {
                 device_id,
                 device_event,
             },
-        });
+        );
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
-        let mut handle = WinPlatformHandle {
-            event_loop,
-            windows: &mut self.windows,
-        };
-
         // Close the application if there are no windows
-        if handle.windows.is_empty() {
-            handle.event_loop.exit();
+        if self.windows.is_empty() {
+            tracing::info!("All windows closed, exiting");
+            event_loop.exit();
         }
 
-        self.controller.run(WinPlatformEvent {
-            handle: &mut handle,
-            kind: WinPlatformEventKind::AboutToWait,
-        });
+        #[cfg(feature = "record")]
+        self.replay_iteration(event_loop);
+        self.dispatch(event_loop, WinPlatformEventKind::AboutToWait);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{borrow::Borrow, sync::Arc};
+
+    use winit::window::Window;
+
+    use super::*;
+
+    /// Stands for a window pointer, lookups never borrow the window.
+    #[derive(Debug)]
+    pub(crate) struct FakeWindow;
+
+    impl Borrow<Window> for FakeWindow {
+        fn borrow(&self) -> &Window {
+            unreachable!("Fake window borrowed")
+        }
+    }
+
+    /// Fake windows with the given ids, in creation order.
+    pub(crate) fn fake_windows(ids: &[u64]) -> WindowMap {
+        (0..)
+            .zip(ids)
+            .map(|(index, id)| {
+                let entry = WindowEntry {
+                    ptr: Box::new(FakeWindow),
+                    type_name: type_name::<FakeWindow>(),
+                    index,
+                };
+                (WindowId::from(*id), entry)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn window_ptr_of_the_created_type() {
+        assert!(get_window_ptr::<FakeWindow>(&fake_windows(&[0]), WindowId::from(0)).is_ok());
+    }
+
+    #[test]
+    fn window_ptr_type_mismatch() {
+        let error =
+            get_window_ptr::<Arc<Window>>(&fake_windows(&[0]), WindowId::from(0)).unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::WindowPtrType { expected, found }
+                if expected == type_name::<Arc<Window>>() && found == type_name::<FakeWindow>()
+        ));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Window pointer is a {}, not a {}",
+                type_name::<FakeWindow>(),
+                type_name::<Arc<Window>>()
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_window_ptr() {
+        let error =
+            get_window_ptr::<FakeWindow>(&fake_windows(&[0]), WindowId::from(1)).unwrap_err();
+        assert!(matches!(error, Error::UnknownWindow { .. }));
     }
 }
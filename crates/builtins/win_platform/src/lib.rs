@@ -1,23 +1,42 @@
-use internals::WindowVec;
-use nuum_core::platform::{Platform, PlatformEvent};
+use std::{any::type_name, marker::PhantomData, time::Duration};
+
+use internals::{WindowEntry, WindowMap};
+use nuum_core::{
+    error::{Error, Result},
+    event::Propagation,
+    platform::{Platform, PlatformEvent},
+};
 use winit::{
-    event::{DeviceEvent, DeviceId, WindowEvent},
-    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    event::{DeviceEvent, DeviceId, StartCause, WindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
     window::{Window, WindowAttributes, WindowId},
 };
 
 pub mod builtins;
 mod internals;
+#[cfg(feature = "record")]
+pub mod record;
+pub mod window_manager;
 
 pub use winit;
 
-pub struct WinPlatformEvent<'a> {
+pub struct WinPlatformEvent<'a, T: 'static = ()> {
     pub handle: &'a mut WinPlatformHandle<'a>,
-    pub kind: WinPlatformEventKind,
+    pub kind: WinPlatformEventKind<T>,
+    pub handled: bool,
+    proxy: &'a EventLoopProxy<T>,
+    dt: Option<Duration>,
 }
 
-pub enum WinPlatformEventKind {
+pub enum WinPlatformEventKind<T = ()> {
+    /// First resume of the application, windows should be created here.
     Init,
+    /// Application resumed after a [`WinPlatformEventKind::Suspended`], surfaces can be acquired again.
+    Resumed,
+    /// Surfaces should be released, e.g. on Android when the app goes to background.
+    Suspended,
+    /// Start of an event loop iteration, before the events of this iteration.
+    NewEvents(StartCause),
     WindowEvent {
         window_id: WindowId,
         window_event: WindowEvent,
@@ -27,91 +46,273 @@ pub enum WinPlatformEventKind {
         device_event: DeviceEvent,
     },
     AboutToWait,
+    /// Last event before the event loop exits.
+    Exiting,
+    /// The system is low on memory, caches should be released.
+    MemoryWarning,
+    /// Sent through a [`WinPlatformEvent::proxy`], e.g. from a background thread.
+    User(T),
 }
 
-pub struct WinPlatform {
+/// Window platform backed by winit, `T` is the type of the user events sent through [`WinPlatformEvent::proxy`].
+/// <br/>
+/// The event loop is created when the platform runs, on the main thread, and only once per process.
+pub struct WinPlatform<T: 'static = ()> {
+    /// Initial control flow, ports can change it at runtime with [`WinPlatformHandle::set_control_flow`].
     pub control_flow: ControlFlow,
+    #[cfg(feature = "record")]
+    replay: Option<nuum_headless_platform::Recording>,
+    _marker: PhantomData<fn() -> T>,
 }
 
 pub struct WinPlatformHandle<'a> {
     event_loop: &'a ActiveEventLoop,
-    windows: &'a mut WindowVec,
+    windows: &'a mut WindowMap,
+    /// Number of windows created since the platform started, removed ones included.
+    created_windows: &'a mut u64,
+}
+
+/// Id of a window created with the pointer type `P`, e.g. `Arc<Window>`, to get the pointer back without type
+/// mismatch, see [`WinPlatformHandle::get`].
+pub struct TypedWindowId<P> {
+    id: WindowId,
+    _marker: PhantomData<fn() -> P>,
+}
+
+impl<P> TypedWindowId<P> {
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+}
+
+impl<P> Clone for TypedWindowId<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for TypedWindowId<P> {}
+
+impl<P> PartialEq for TypedWindowId<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<P> Eq for TypedWindowId<P> {}
+
+impl<P> std::hash::Hash for TypedWindowId<P> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<P> std::fmt::Debug for TypedWindowId<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedWindowId")
+            .field(&type_name::<P>())
+            .field(&self.id)
+            .finish()
+    }
+}
+
+impl<P> From<TypedWindowId<P>> for WindowId {
+    fn from(typed: TypedWindowId<P>) -> Self {
+        typed.id
+    }
 }
 
 impl WinPlatformHandle<'_> {
+    /// Control flow of the next event loop iterations, initially [`WinPlatform::control_flow`]. <br/>
+    /// Persists until changed, e.g. set [`ControlFlow::Wait`] to sleep until the next event.
+    pub fn set_control_flow(&self, control_flow: ControlFlow) {
+        self.event_loop.set_control_flow(control_flow);
+    }
+
+    pub fn control_flow(&self) -> ControlFlow {
+        self.event_loop.control_flow()
+    }
+
+    /// Panics if the window cannot be created, see [`WinPlatformHandle::try_create_window`].
     pub fn create_window(&mut self, attrs: WindowAttributes) -> &Window {
         self.create_window_ptr(|w| w, attrs)
     }
 
+    pub fn try_create_window(&mut self, attrs: WindowAttributes) -> Result<&Window> {
+        self.try_create_window_ptr(|w| w, attrs)
+    }
+
     pub fn create_window_ptr<T: WindowPtr>(
         &mut self,
         builder: impl FnOnce(Window) -> T,
         attrs: WindowAttributes,
     ) -> &T {
+        self.try_create_window_ptr(builder, attrs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_create_window_ptr<T: WindowPtr>(
+        &mut self,
+        builder: impl FnOnce(Window) -> T,
+        attrs: WindowAttributes,
+    ) -> Result<&T> {
+        let id = self.try_create_typed_window(builder, attrs)?;
+        Ok(self.get(id).expect("Window inserted right before lookup"))
+    }
+
+    pub fn try_create_typed_window<T: WindowPtr>(
+        &mut self,
+        builder: impl FnOnce(Window) -> T,
+        attrs: WindowAttributes,
+    ) -> Result<TypedWindowId<T>> {
+        let title = attrs.title.clone();
         let window = self
             .event_loop
             .create_window(attrs)
-            .expect("Window creation failed");
+            .map_err(|e| Error::CreateWindow {
+                title,
+                source: e.into(),
+            })?;
         let id = window.id();
-        self.windows.push(Box::new(builder(window)));
-        self.get_window_ptr(id).expect("Window creation failed")
+        tracing::info!(?id, title = window.title(), "Window created");
+        self.windows.insert(
+            id,
+            WindowEntry {
+                ptr: Box::new(builder(window)),
+                type_name: type_name::<T>(),
+                index: *self.created_windows,
+            },
+        );
+        *self.created_windows += 1;
+        Ok(TypedWindowId {
+            id,
+            _marker: PhantomData,
+        })
     }
 
-    pub fn get_window(&mut self, id: WindowId) -> Option<&Window> {
+    pub fn get_window(&self, id: WindowId) -> Option<&Window> {
         self.get_dyn_window_ptr(id).map(|w| w.borrow())
     }
 
     /// If you are using a referenced counted pointer, this will not drop the window and you will need to drop every instance manually or only use `Weak`.
     pub fn remove_window(&mut self, id: WindowId) -> Option<Box<dyn WindowPtr>> {
-        Some(
-            self.windows.swap_remove(
-                self.windows
-                    .iter()
-                    .position(|w| w.as_ref().borrow().id() == id)?,
-            ),
-        )
+        let entry = self.windows.remove(&id)?;
+        tracing::debug!(?id, "Window removed");
+        Some(entry.ptr)
+    }
+
+    /// Same as [`WinPlatformHandle::remove_window`], returning the typed pointer.
+    pub fn remove_typed_window<T: WindowPtr>(&mut self, id: TypedWindowId<T>) -> Option<T> {
+        self.remove_window(id.id)?
+            .into_any()
+            .downcast()
+            .ok()
+            .map(|ptr| *ptr)
+    }
+
+    /// Checks the pointer type of the window, to look it up with [`WinPlatformHandle::get`].
+    pub fn typed_window<T: WindowPtr>(&self, id: WindowId) -> Result<TypedWindowId<T>> {
+        self.get_window_ptr::<T>(id).map(|_| TypedWindowId {
+            id,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns `None` once the window is removed.
+    pub fn get<T: WindowPtr>(&self, id: TypedWindowId<T>) -> Option<&T> {
+        self.get_window_ptr(id.id).ok()
+    }
+
+    pub fn get_window_ptr<T: WindowPtr>(&self, id: WindowId) -> Result<&T> {
+        internals::get_window_ptr(self.windows, id)
     }
 
-    pub fn get_window_ptr<T: WindowPtr>(&mut self, id: WindowId) -> Option<&T> {
-        self.get_dyn_window_ptr(id)
-            .map(|w| w.as_any_ref().downcast_ref::<T>().expect("Failed to downcast window pointer, the specified window pointer type doesn't match the actual window ptr type"))
+    pub fn get_dyn_window_ptr(&self, id: WindowId) -> Option<&dyn WindowPtr> {
+        self.windows.get(&id).map(|entry| entry.ptr.as_ref())
     }
 
-    pub fn get_dyn_window_ptr(&mut self, id: WindowId) -> Option<&dyn WindowPtr> {
-        self.windows
-            .iter()
-            .find_map(|w| (w.as_ref().borrow().id() == id).then_some(w.as_ref()))
+    pub fn window_ids(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.keys().copied()
+    }
+
+    /// Creation index of the window, the number of windows created before it since the platform started.
+    pub fn window_index(&self, id: WindowId) -> Option<u64> {
+        self.windows.get(&id).map(|entry| entry.index)
+    }
+}
+
+impl<T> WinPlatformEvent<'_, T> {
+    /// Cloneable and thread safe sender of [`WinPlatformEventKind::User`] events, waking the event loop. <br/>
+    /// Sending fails once the event loop exited.
+    pub fn proxy(&self) -> EventLoopProxy<T> {
+        self.proxy.clone()
     }
 }
 
-impl<'a> AsMut<WinPlatformHandle<'a>> for WinPlatformEvent<'a> {
+impl<'a, T> AsMut<WinPlatformHandle<'a>> for WinPlatformEvent<'a, T> {
     fn as_mut(&mut self) -> &mut WinPlatformHandle<'a> {
         self.handle
     }
 }
 
-impl Default for WinPlatform {
+impl<T: 'static> Default for WinPlatform<T> {
     fn default() -> Self {
+        Self::with_user_event()
+    }
+}
+
+impl WinPlatform {
+    /// Platform without user events, see [`WinPlatform::with_user_event`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: 'static> WinPlatform<T> {
+    /// Platform receiving user events of type `T`, e.g. `WinPlatform::<MyEvent>::with_user_event()`.
+    pub fn with_user_event() -> Self {
         Self {
             control_flow: ControlFlow::Poll,
+            #[cfg(feature = "record")]
+            replay: None,
+            _marker: PhantomData,
         }
     }
-}
 
-impl Platform for WinPlatform {
-    type Event<'a> = WinPlatformEvent<'a>;
-
-    fn run<T: for<'a> nuum_core::Controller<Self::Event<'a>>>(&mut self, controller: &mut T) {
-        let event_loop = EventLoop::new().unwrap();
-        let mut runner = internals::WinPlatformRunner::<T>::new(controller);
+    /// Same as [`Platform::run`], returning event loop failures instead of panicking. <br/>
+    /// Fails if an event loop was already created by the process.
+    pub fn try_run<C: for<'a> nuum_core::Controller<WinPlatformEvent<'a, T>>>(
+        &mut self,
+        controller: &mut C,
+    ) -> Result<()> {
+        let _span = tracing::info_span!("event_loop").entered();
+        let event_loop = EventLoop::with_user_event()
+            .build()
+            .map_err(|e| Error::EventLoop { source: e.into() })?;
+        let mut runner =
+            internals::WinPlatformRunner::<C, T>::new(controller, event_loop.create_proxy());
+        #[cfg(feature = "record")]
+        {
+            runner.replay = self.replay.clone().map(record::Replay::new);
+        }
 
         event_loop.set_control_flow(self.control_flow);
 
-        event_loop.run_app(&mut runner).unwrap();
+        event_loop
+            .run_app(&mut runner)
+            .map_err(|e| Error::EventLoop { source: e.into() })
+    }
+}
+
+impl<T: 'static> Platform for WinPlatform<T> {
+    type Event<'a> = WinPlatformEvent<'a, T>;
+
+    fn run<C: for<'a> nuum_core::Controller<Self::Event<'a>>>(&mut self, controller: &mut C) {
+        self.try_run(controller).unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
-impl PlatformEvent for WinPlatformEvent<'_> {
+impl<T> PlatformEvent for WinPlatformEvent<'_, T> {
     fn exit(&self) {
         self.handle.event_loop.exit();
     }
@@ -122,13 +323,32 @@ impl PlatformEvent for WinPlatformEvent<'_> {
             WinPlatformEventKind::Init | WinPlatformEventKind::AboutToWait
         )
     }
+
+    /// Recorded time step when replaying a recording, `None` for live events.
+    fn dt(&self) -> Option<Duration> {
+        self.dt
+    }
+}
+
+impl<T> Propagation for WinPlatformEvent<'_, T> {
+    fn is_handled(&self) -> bool {
+        self.handled
+    }
+
+    fn set_handled(&mut self) {
+        self.handled = true;
+    }
 }
 
 pub trait WindowPtr: std::any::Any + std::borrow::Borrow<Window> {
     fn as_any_ref(&self) -> &dyn std::any::Any;
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any>;
 }
 impl<T: std::borrow::Borrow<Window> + 'static> WindowPtr for T {
     fn as_any_ref(&self) -> &dyn std::any::Any {
         self
     }
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
 }
@@ -0,0 +1,440 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use nuum_core::Port;
+use nuum_headless_platform::{
+    DeviceEvent, HeadlessPlatformEventKind, MouseButton, Recording, ScriptedEvent, WindowEvent,
+    WindowId,
+};
+use winit::{
+    dpi::PhysicalPosition,
+    event::{self, ElementState, MouseScrollDelta, TouchPhase},
+    keyboard::Key,
+    window,
+};
+
+use crate::{internals::WindowMap, WinPlatform, WinPlatformEvent, WinPlatformEventKind};
+
+/// Records the platform event stream, to be replayed by the headless platform or [`WinPlatform::with_replay`].
+/// <br/>
+/// The recording is saved when the port is dropped, or manually with [`RecordPort::save`].
+///
+/// Windows are given the virtual id of their creation index, as the headless platform does, see
+/// [`crate::WinPlatformHandle::window_index`]. Redraw requests are not recorded, they are emitted on replay by
+/// ports requesting them.
+pub struct RecordPort {
+    path: PathBuf,
+    start: Instant,
+    recording: Recording,
+    windows: HashMap<window::WindowId, WindowId>,
+}
+
+impl RecordPort {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            start: Instant::now(),
+            recording: Recording::default(),
+            windows: HashMap::new(),
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        self.recording.save(&self.path)
+    }
+
+    fn record<T>(&mut self, event: &WinPlatformEvent<T>) -> Option<HeadlessPlatformEventKind> {
+        Some(match &event.kind {
+            WinPlatformEventKind::Init => HeadlessPlatformEventKind::Init,
+            WinPlatformEventKind::AboutToWait => HeadlessPlatformEventKind::AboutToWait,
+            WinPlatformEventKind::WindowEvent {
+                window_id,
+                window_event,
+            } => {
+                let window_event = convert_window_event(window_event)?;
+                // Kept once known, events can still be sent for a removed window
+                let window_id = match self.windows.get(window_id) {
+                    Some(id) => *id,
+                    None => {
+                        let id = WindowId(event.handle.window_index(*window_id)?);
+                        *self.windows.entry(*window_id).or_insert(id)
+                    }
+                };
+                HeadlessPlatformEventKind::WindowEvent {
+                    window_id,
+                    window_event,
+                }
+            }
+            WinPlatformEventKind::DeviceEvent { device_event, .. } => match device_event {
+                event::DeviceEvent::MouseMotion { delta: (dx, dy) } => {
+                    HeadlessPlatformEventKind::DeviceEvent(DeviceEvent::MouseMotion {
+                        dx: *dx,
+                        dy: *dy,
+                    })
+                }
+                _ => return None,
+            },
+            // Lifecycle events have no headless equivalent, replays run as a single uninterrupted session
+            WinPlatformEventKind::Resumed
+            | WinPlatformEventKind::Suspended
+            | WinPlatformEventKind::NewEvents(_)
+            | WinPlatformEventKind::Exiting
+            | WinPlatformEventKind::MemoryWarning
+            | WinPlatformEventKind::User(_) => return None,
+        })
+    }
+}
+
+impl Drop for RecordPort {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            tracing::warn!(
+                path = %self.path.display(),
+                "Failed to save event recording: {e}"
+            );
+        }
+    }
+}
+
+impl<'a, 'b, T, Inner> Port<'a, WinPlatformEvent<'b, T>, Inner> for RecordPort {
+    fn port(&mut self, event: &mut WinPlatformEvent<T>, _: &mut Inner) {
+        if let Some(kind) = self.record(event) {
+            self.recording.events.push(ScriptedEvent {
+                time: Some(self.start.elapsed()),
+                kind,
+            });
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
+
+fn convert_window_event(window_event: &event::WindowEvent) -> Option<WindowEvent> {
+    Some(match window_event {
+        event::WindowEvent::Resized(size) => WindowEvent::Resized {
+            width: size.width,
+            height: size.height,
+        },
+        event::WindowEvent::Focused(focused) => WindowEvent::Focused(*focused),
+        event::WindowEvent::CloseRequested => WindowEvent::CloseRequested,
+        event::WindowEvent::CursorMoved { position, .. } => WindowEvent::CursorMoved {
+            x: position.x,
+            y: position.y,
+        },
+        event::WindowEvent::MouseInput { state, button, .. } => WindowEvent::MouseInput {
+            button: match button {
+                event::MouseButton::Left => MouseButton::Left,
+                event::MouseButton::Right => MouseButton::Right,
+                event::MouseButton::Middle => MouseButton::Middle,
+                event::MouseButton::Back => MouseButton::Other(3),
+                event::MouseButton::Forward => MouseButton::Other(4),
+                event::MouseButton::Other(id) => MouseButton::Other(*id),
+            },
+            pressed: state.is_pressed(),
+        },
+        event::WindowEvent::MouseWheel { delta, .. } => {
+            let (dx, dy) = match delta {
+                MouseScrollDelta::LineDelta(x, y) => (*x, *y),
+                MouseScrollDelta::PixelDelta(p) => (p.x as f32, p.y as f32),
+            };
+            WindowEvent::MouseWheel { dx, dy }
+        }
+        event::WindowEvent::KeyboardInput { event, .. } => WindowEvent::KeyboardInput {
+            key: match &event.logical_key {
+                Key::Character(c) => c.to_string(),
+                key => format!("{key:?}"),
+            },
+            pressed: event.state.is_pressed(),
+        },
+        event::WindowEvent::Ime(event::Ime::Commit(text)) => WindowEvent::Text(text.clone()),
+        _ => return None,
+    })
+}
+
+impl<T: 'static> WinPlatform<T> {
+    /// Replays `recording` in place of the live input and time, then exits. <br/>
+    /// Windows are created by the ports as in a live run, the virtual ids of the recording standing for their
+    /// creation index. The recorded update and redraw time steps are given by [`nuum_core::platform::PlatformEvent::dt`].
+    /// Keyboard input cannot be replayed on the window platform, and wheel deltas are replayed as line deltas, use
+    /// the headless platform to replay them.
+    pub fn with_replay(mut self, recording: Recording) -> Self {
+        self.replay = Some(recording);
+        self
+    }
+}
+
+/// Recorded events and time of a [`WinPlatform::with_replay`] run.
+pub(crate) struct Replay {
+    events: std::vec::IntoIter<ScriptedEvent>,
+    time: Option<Duration>,
+    last_update: Option<Duration>,
+    last_redraws: HashMap<window::WindowId, Duration>,
+    skipped_keys: bool,
+}
+
+impl Replay {
+    pub(crate) fn new(recording: Recording) -> Self {
+        // The live Init stands for the recorded one
+        let mut events = recording.events.into_iter();
+        let starts_with_init = events
+            .as_slice()
+            .first()
+            .is_some_and(|event| event.kind == HeadlessPlatformEventKind::Init);
+        let time = match starts_with_init {
+            true => events.next().and_then(|event| event.time),
+            false => None,
+        };
+
+        Self {
+            events,
+            time,
+            last_update: None,
+            last_redraws: HashMap::new(),
+            skipped_keys: false,
+        }
+    }
+
+    /// Recorded events until the next `AboutToWait`, `None` once the recording is over.
+    pub(crate) fn next_iteration(&mut self) -> Option<Vec<HeadlessPlatformEventKind>> {
+        if self.events.as_slice().is_empty() {
+            return None;
+        }
+
+        let mut events = Vec::new();
+        for ScriptedEvent { time, kind } in self.events.by_ref() {
+            self.time = time.or(self.time);
+            match kind {
+                HeadlessPlatformEventKind::AboutToWait => break,
+                HeadlessPlatformEventKind::Init => (),
+                kind => events.push(kind),
+            }
+        }
+        Some(events)
+    }
+
+    /// Recorded time since the previous update, or previous redraw of the same window.
+    pub(crate) fn dt<T>(&mut self, kind: &WinPlatformEventKind<T>) -> Option<Duration> {
+        let now = self.time?;
+        let last = match kind {
+            WinPlatformEventKind::Init | WinPlatformEventKind::AboutToWait => {
+                self.last_update.replace(now)
+            }
+            WinPlatformEventKind::WindowEvent {
+                window_id,
+                window_event: event::WindowEvent::RedrawRequested,
+            } => self.last_redraws.insert(*window_id, now),
+            _ => return None,
+        };
+        Some(now.saturating_sub(last.unwrap_or(now)))
+    }
+
+    /// Window platform event of a recorded event, `None` if it cannot be replayed or its window is not created.
+    pub(crate) fn event<T>(
+        &mut self,
+        kind: HeadlessPlatformEventKind,
+        windows: &WindowMap,
+    ) -> Option<WinPlatformEventKind<T>> {
+        let device_id = event::DeviceId::dummy();
+        let (window_id, window_event) = match kind {
+            HeadlessPlatformEventKind::WindowEvent {
+                window_id,
+                window_event,
+            } => (window_id, window_event),
+            HeadlessPlatformEventKind::DeviceEvent(DeviceEvent::MouseMotion { dx, dy }) => {
+                return Some(WinPlatformEventKind::DeviceEvent {
+                    device_id,
+                    device_event: event::DeviceEvent::MouseMotion { delta: (dx, dy) },
+                });
+            }
+            HeadlessPlatformEventKind::Init | HeadlessPlatformEventKind::AboutToWait => {
+                return None
+            }
+        };
+
+        let window_event = match window_event {
+            WindowEvent::Focused(focused) => event::WindowEvent::Focused(focused),
+            WindowEvent::CloseRequested => event::WindowEvent::CloseRequested,
+            WindowEvent::CursorMoved { x, y } => event::WindowEvent::CursorMoved {
+                device_id,
+                position: PhysicalPosition::new(x, y),
+            },
+            WindowEvent::MouseInput { button, pressed } => event::WindowEvent::MouseInput {
+                device_id,
+                state: match pressed {
+                    true => ElementState::Pressed,
+                    false => ElementState::Released,
+                },
+                button: match button {
+                    MouseButton::Left => event::MouseButton::Left,
+                    MouseButton::Right => event::MouseButton::Right,
+                    MouseButton::Middle => event::MouseButton::Middle,
+                    MouseButton::Other(3) => event::MouseButton::Back,
+                    MouseButton::Other(4) => event::MouseButton::Forward,
+                    MouseButton::Other(id) => event::MouseButton::Other(id),
+                },
+            },
+            WindowEvent::MouseWheel { dx, dy } => event::WindowEvent::MouseWheel {
+                device_id,
+                delta: MouseScrollDelta::LineDelta(dx, dy),
+                phase: TouchPhase::Moved,
+            },
+            WindowEvent::Text(text) => event::WindowEvent::Ime(event::Ime::Commit(text)),
+            // The live windows keep their size, and are redrawn on request
+            WindowEvent::Resized { .. } | WindowEvent::RedrawRequested => return None,
+            // Winit key events can only be built by winit
+            WindowEvent::KeyboardInput { .. } => {
+                if !std::mem::replace(&mut self.skipped_keys, true) {
+                    tracing::warn!(
+                        "Keyboard input cannot be replayed on the window platform, skipping it"
+                    );
+                }
+                return None;
+            }
+        };
+
+        let window_id = windows
+            .iter()
+            .find(|(_, entry)| entry.index == window_id.0)
+            .map(|(id, _)| *id)?;
+        Some(WinPlatformEventKind::WindowEvent {
+            window_id,
+            window_event,
+        })
+    }
+}
+
+/// Live window events still dispatched when replaying, the input events are replaced by the recorded ones.
+pub(crate) fn is_live(window_event: &event::WindowEvent) -> bool {
+    matches!(
+        window_event,
+        event::WindowEvent::RedrawRequested
+            | event::WindowEvent::Resized(_)
+            | event::WindowEvent::ScaleFactorChanged { .. }
+            | event::WindowEvent::Moved(_)
+            | event::WindowEvent::Occluded(_)
+            | event::WindowEvent::ThemeChanged(_)
+            | event::WindowEvent::CloseRequested
+            | event::WindowEvent::Destroyed
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internals::tests::fake_windows;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn at(ms_: u64, kind: HeadlessPlatformEventKind) -> ScriptedEvent {
+        ScriptedEvent {
+            time: Some(ms(ms_)),
+            kind,
+        }
+    }
+
+    fn window_event(id: u64, window_event: WindowEvent) -> HeadlessPlatformEventKind {
+        HeadlessPlatformEventKind::WindowEvent {
+            window_id: WindowId(id),
+            window_event,
+        }
+    }
+
+    fn redraw(id: u64) -> WinPlatformEventKind {
+        WinPlatformEventKind::WindowEvent {
+            window_id: window::WindowId::from(id),
+            window_event: event::WindowEvent::RedrawRequested,
+        }
+    }
+
+    fn replay() -> Replay {
+        Replay::new(Recording {
+            events: vec![
+                at(100, HeadlessPlatformEventKind::Init),
+                at(110, window_event(0, WindowEvent::Focused(true))),
+                at(116, HeadlessPlatformEventKind::AboutToWait),
+                at(140, window_event(1, WindowEvent::CloseRequested)),
+                at(150, HeadlessPlatformEventKind::AboutToWait),
+            ],
+            ..Recording::default()
+        })
+    }
+
+    #[test]
+    fn iterations_end_on_about_to_wait() {
+        let mut replay = replay();
+        assert_eq!(
+            replay.next_iteration(),
+            Some(vec![window_event(0, WindowEvent::Focused(true))])
+        );
+        assert_eq!(
+            replay.next_iteration(),
+            Some(vec![window_event(1, WindowEvent::CloseRequested)])
+        );
+        assert_eq!(replay.next_iteration(), None);
+    }
+
+    #[test]
+    fn time_steps_follow_the_recording() {
+        let mut replay = replay();
+        assert_eq!(replay.dt(&WinPlatformEventKind::<()>::Init), Some(ms(0)));
+
+        replay.next_iteration();
+        assert_eq!(replay.dt(&redraw(7)), Some(ms(0)));
+        assert_eq!(
+            replay.dt(&WinPlatformEventKind::<()>::AboutToWait),
+            Some(ms(16))
+        );
+
+        // Redraws are timed per window
+        replay.next_iteration();
+        assert_eq!(replay.dt(&redraw(7)), Some(ms(34)));
+        assert_eq!(replay.dt(&redraw(8)), Some(ms(0)));
+        assert_eq!(
+            replay.dt(&WinPlatformEventKind::<()>::AboutToWait),
+            Some(ms(34))
+        );
+        assert_eq!(replay.dt(&WinPlatformEventKind::<()>::Resumed), None);
+    }
+
+    #[test]
+    fn virtual_ids_stand_for_the_creation_order() {
+        let mut replay = replay();
+        let windows = fake_windows(&[42, 7]);
+
+        let Some(WinPlatformEventKind::<()>::WindowEvent {
+            window_id,
+            window_event: event::WindowEvent::CloseRequested,
+        }) = replay.event(window_event(1, WindowEvent::CloseRequested), &windows)
+        else {
+            panic!("Close request not replayed");
+        };
+        assert_eq!(window_id, window::WindowId::from(7));
+
+        // Windows not created by this run are skipped
+        let event = replay.event::<()>(window_event(2, WindowEvent::CloseRequested), &windows);
+        assert!(event.is_none());
+    }
+
+    #[test]
+    fn keyboard_input_is_skipped() {
+        let mut replay = replay();
+        let key = WindowEvent::KeyboardInput {
+            key: "a".to_owned(),
+            pressed: true,
+        };
+        let event = replay.event::<()>(window_event(0, key), &fake_windows(&[0]));
+        assert!(event.is_none());
+        assert!(replay.skipped_keys);
+    }
+}
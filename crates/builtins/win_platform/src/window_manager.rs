@@ -0,0 +1,404 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use nuum_core::{error::Result, Controller, Port};
+use winit::{
+    event::WindowEvent,
+    raw_window_handle::HasWindowHandle,
+    window::{WindowAttributes, WindowId},
+};
+
+use crate::{WinPlatformEvent, WinPlatformEventKind, WinPlatformHandle};
+
+/// Declares a window of a [`WindowManagerPort`], identified by its logical `name`.
+#[derive(Debug, Clone)]
+pub struct WindowSpec {
+    pub name: String,
+    pub attrs: WindowAttributes,
+    /// Name of the parent window, the window is opened after its parent and closed with it.
+    pub parent: Option<String>,
+}
+
+impl WindowSpec {
+    pub fn new(name: impl Into<String>, attrs: WindowAttributes) -> Self {
+        Self {
+            name: name.into(),
+            attrs,
+            parent: None,
+        }
+    }
+
+    pub fn with_parent(mut self, parent: impl Into<String>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+}
+
+/// Sent to the inner controller when a window of a [`WindowManagerPort`] is created.
+#[derive(Debug, Clone)]
+pub struct WindowOpened {
+    pub name: String,
+    pub id: WindowId,
+}
+
+/// Sent to the inner controller when a window of a [`WindowManagerPort`] is removed, children before their parent.
+#[derive(Debug, Clone)]
+pub struct WindowClosed {
+    pub name: String,
+    pub id: WindowId,
+}
+
+/// Creates and removes the windows of [`Windows::apply`].
+trait WindowHost {
+    fn open_window(
+        &mut self,
+        attrs: WindowAttributes,
+        parent: Option<WindowId>,
+    ) -> Result<WindowId>;
+
+    fn close_window(&mut self, id: WindowId);
+}
+
+impl WindowHost for WinPlatformHandle<'_> {
+    fn open_window(
+        &mut self,
+        mut attrs: WindowAttributes,
+        parent: Option<WindowId>,
+    ) -> Result<WindowId> {
+        let parent = parent.and_then(|id| self.get_window(id));
+        if let Some(Ok(parent)) = parent.map(|w| w.window_handle()) {
+            // SAFETY: the manager closes children before their parent
+            attrs = unsafe { attrs.with_parent_window(Some(parent.as_raw())) };
+        }
+        self.try_create_window_ptr(Arc::new, attrs)
+            .map(|window| window.id())
+    }
+
+    fn close_window(&mut self, id: WindowId) {
+        self.remove_window(id);
+    }
+}
+
+struct WindowsState {
+    /// Declared windows, in declaration order.
+    specs: Vec<WindowSpec>,
+    /// Opened windows, in opening order so parents come before their children.
+    open: Vec<(String, WindowId)>,
+}
+
+/// Shared handle to the windows of a [`WindowManagerPort`], clones manage the same windows. <br/>
+/// Changes are applied by the port on the next platform event.
+#[derive(Clone)]
+pub struct Windows(Arc<Mutex<WindowsState>>);
+
+impl Windows {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(WindowsState {
+            specs: Vec::new(),
+            open: Vec::new(),
+        })))
+    }
+
+    /// Declares a window, replacing the spec with the same name. <br/>
+    /// The attributes of an already opened window are not updated, close it first to recreate it.
+    pub fn open(&self, spec: WindowSpec) {
+        let mut state = self.lock();
+        match state.specs.iter_mut().find(|s| s.name == spec.name) {
+            Some(existing) => *existing = spec,
+            None => state.specs.push(spec),
+        }
+    }
+
+    /// Removes the window and its children from the declared windows, returns false if it was not declared.
+    pub fn close(&self, name: &str) -> bool {
+        let mut state = self.lock();
+        let len = state.specs.len();
+        let mut closed = vec![name.to_owned()];
+        while let Some(name) = closed.pop() {
+            state.specs.retain(|spec| {
+                let child = spec.parent.as_deref() == Some(name.as_str());
+                if child {
+                    closed.push(spec.name.clone());
+                }
+                spec.name != name && !child
+            });
+        }
+        state.specs.len() != len
+    }
+
+    pub fn is_declared(&self, name: &str) -> bool {
+        self.lock().specs.iter().any(|spec| spec.name == name)
+    }
+
+    pub fn is_open(&self, name: &str) -> bool {
+        self.id(name).is_some()
+    }
+
+    pub fn id(&self, name: &str) -> Option<WindowId> {
+        let state = self.lock();
+        state
+            .open
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, id)| *id)
+    }
+
+    pub fn name(&self, id: WindowId) -> Option<String> {
+        let state = self.lock();
+        state
+            .open
+            .iter()
+            .find(|(_, i)| *i == id)
+            .map(|(n, _)| n.clone())
+    }
+
+    /// Names of the opened windows, parents before their children.
+    pub fn names(&self) -> Vec<String> {
+        self.lock().open.iter().map(|(n, _)| n.clone()).collect()
+    }
+
+    /// Opens and closes windows to match the declared ones.
+    fn apply(&self, host: &mut impl WindowHost) -> (Vec<WindowOpened>, Vec<WindowClosed>) {
+        let mut state = self.lock();
+        let WindowsState { specs, open } = &mut *state;
+
+        let mut closed = Vec::new();
+        for (name, id) in std::mem::take(open).into_iter().rev() {
+            if specs.iter().any(|spec| spec.name == name) {
+                open.insert(0, (name, id));
+            } else {
+                host.close_window(id);
+                closed.push(WindowClosed { name, id });
+            }
+        }
+
+        // Children can be declared before their parent, loop until no window can be opened
+        let mut opened = Vec::new();
+        let mut failed = Vec::new();
+        loop {
+            let pending = specs.iter().find(|spec| {
+                !open.iter().any(|(n, _)| *n == spec.name)
+                    && !failed.contains(&spec.name)
+                    && spec
+                        .parent
+                        .as_ref()
+                        .is_none_or(|parent| open.iter().any(|(n, _)| n == parent))
+            });
+            let Some(spec) = pending else {
+                break;
+            };
+
+            let parent = spec
+                .parent
+                .as_ref()
+                .and_then(|parent| open.iter().find(|(n, _)| n == parent))
+                .map(|(_, id)| *id);
+            match host.open_window(spec.attrs.clone(), parent) {
+                Ok(id) => {
+                    open.push((spec.name.clone(), id));
+                    opened.push(WindowOpened {
+                        name: spec.name.clone(),
+                        id,
+                    });
+                }
+                Err(e) => {
+                    tracing::error!(name = spec.name, "Failed to open window: {e}");
+                    failed.push(spec.name.clone());
+                }
+            }
+        }
+        // Not retried on every event, the spec has to be declared again
+        if !failed.is_empty() {
+            drop(state);
+            for name in failed {
+                self.close(&name);
+            }
+        }
+
+        (opened, closed)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, WindowsState> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Opens, closes and tracks windows from their [`WindowSpec`]s, see [`Windows`]. <br/>
+/// A window closed by the user is removed with its children, the app exits once every window is closed.
+/// Windows are created with an `Arc<Window>` pointer, as expected by the renderer.
+pub struct WindowManagerPort {
+    windows: Windows,
+    initialized: bool,
+}
+
+impl Default for WindowManagerPort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowManagerPort {
+    pub fn new() -> Self {
+        Self {
+            windows: Windows::new(),
+            initialized: false,
+        }
+    }
+
+    pub fn with_window(self, spec: WindowSpec) -> Self {
+        self.windows.open(spec);
+        self
+    }
+
+    pub fn windows(&self) -> Windows {
+        self.windows.clone()
+    }
+}
+
+impl<'a, 'b, T, Inner: Controller<WindowOpened> + Controller<WindowClosed>>
+    Port<'a, WinPlatformEvent<'b, T>, Inner> for WindowManagerPort
+{
+    fn port(&mut self, event: &mut WinPlatformEvent<T>, inner: &mut Inner) {
+        match &event.kind {
+            // Windows can only be created once the application is initialized
+            WinPlatformEventKind::Init => self.initialized = true,
+            WinPlatformEventKind::WindowEvent {
+                window_id,
+                window_event: WindowEvent::CloseRequested,
+            } => {
+                if let Some(name) = self.windows.name(*window_id) {
+                    self.windows.close(&name);
+                }
+            }
+            _ => (),
+        }
+        if !self.initialized {
+            return;
+        }
+
+        // Events are sent after the windows are updated, windows declared by the inner controller in response are
+        // applied right away, e.g. so that the app does not exit when the last window is replaced
+        loop {
+            let (opened, closed) = self.windows.apply(&mut *event.handle);
+            if opened.is_empty() && closed.is_empty() {
+                break;
+            }
+            for window in closed {
+                inner.run(window);
+            }
+            for window in opened {
+                inner.run(window);
+            }
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nuum_core::error::Error;
+
+    use super::*;
+
+    /// Records window operations, windows titled "broken" fail to open.
+    #[derive(Default)]
+    struct FakeHost {
+        next_id: u64,
+        log: Vec<String>,
+    }
+
+    impl WindowHost for FakeHost {
+        fn open_window(
+            &mut self,
+            attrs: WindowAttributes,
+            parent: Option<WindowId>,
+        ) -> Result<WindowId> {
+            if attrs.title == "broken" {
+                return Err(Error::CreateWindow {
+                    title: attrs.title,
+                    source: "broken window".into(),
+                });
+            }
+            self.next_id += 1;
+            let parent = parent.map(u64::from);
+            self.log
+                .push(format!("open {} {} {parent:?}", attrs.title, self.next_id));
+            Ok(WindowId::from(self.next_id))
+        }
+
+        fn close_window(&mut self, id: WindowId) {
+            self.log.push(format!("close {}", u64::from(id)));
+        }
+    }
+
+    fn spec(name: &str) -> WindowSpec {
+        WindowSpec::new(name, WindowAttributes::default().with_title(name))
+    }
+
+    fn names<'a>(
+        opened: &'a [WindowOpened],
+        closed: &'a [WindowClosed],
+    ) -> (Vec<&'a str>, Vec<&'a str>) {
+        (
+            opened.iter().map(|w| w.name.as_str()).collect(),
+            closed.iter().map(|w| w.name.as_str()).collect(),
+        )
+    }
+
+    #[test]
+    fn parents_open_before_their_children() {
+        let windows = Windows::new();
+        windows.open(spec("child").with_parent("main"));
+        windows.open(spec("main"));
+        let mut host = FakeHost::default();
+
+        let (opened, closed) = windows.apply(&mut host);
+        assert_eq!(names(&opened, &closed), (vec!["main", "child"], vec![]));
+        assert_eq!(host.log, ["open main 1 None", "open child 2 Some(1)"]);
+        assert_eq!(windows.id("child"), Some(WindowId::from(2)));
+
+        let (opened, closed) = windows.apply(&mut host);
+        assert!(opened.is_empty() && closed.is_empty());
+    }
+
+    #[test]
+    fn children_close_before_their_parent() {
+        let windows = Windows::new();
+        windows.open(spec("main"));
+        windows.open(spec("child").with_parent("main"));
+        windows.open(spec("grandchild").with_parent("child"));
+        windows.open(spec("other"));
+        let mut host = FakeHost::default();
+        windows.apply(&mut host);
+        host.log.clear();
+
+        assert!(windows.close("main"));
+        let (opened, closed) = windows.apply(&mut host);
+        assert_eq!(
+            names(&opened, &closed),
+            (vec![], vec!["grandchild", "child", "main"])
+        );
+        assert_eq!(host.log, ["close 3", "close 2", "close 1"]);
+        assert_eq!(windows.names(), ["other"]);
+    }
+
+    #[test]
+    fn failed_windows_are_undeclared_with_their_children() {
+        let windows = Windows::new();
+        windows.open(spec("main"));
+        windows.open(spec("broken"));
+        windows.open(spec("child").with_parent("broken"));
+        let mut host = FakeHost::default();
+
+        let (opened, closed) = windows.apply(&mut host);
+        assert_eq!(names(&opened, &closed), (vec!["main"], vec![]));
+        assert!(!windows.is_declared("broken"));
+        assert!(!windows.is_declared("child"));
+
+        // Not retried
+        windows.apply(&mut host);
+        assert_eq!(host.log, ["open main 1 None"]);
+    }
+}
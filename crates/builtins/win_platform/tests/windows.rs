@@ -0,0 +1,46 @@
+use nuum_win_platform::{
+    window_manager::{WindowManagerPort, WindowSpec},
+    winit::window::WindowAttributes,
+};
+
+fn spec(name: &str) -> WindowSpec {
+    WindowSpec::new(name, WindowAttributes::default())
+}
+
+#[test]
+fn close_removes_the_window_and_its_children() {
+    let windows = WindowManagerPort::new()
+        .with_window(spec("main"))
+        .with_window(spec("tools").with_parent("main"))
+        .with_window(spec("palette").with_parent("tools"))
+        .with_window(spec("other"))
+        .windows();
+
+    assert!(windows.close("tools"));
+    assert!(windows.is_declared("main"));
+    assert!(!windows.is_declared("tools"));
+    assert!(!windows.is_declared("palette"));
+    assert!(windows.is_declared("other"));
+
+    assert!(!windows.close("tools"));
+    assert!(windows.close("main"));
+    assert!(!windows.is_declared("main"));
+}
+
+#[test]
+fn open_replaces_the_spec_with_the_same_name() {
+    let windows = WindowManagerPort::new()
+        .with_window(spec("main"))
+        .with_window(spec("tools"))
+        .windows();
+
+    // Once replaced, the window is no longer a child of main
+    windows.open(spec("tools").with_parent("main"));
+    windows.open(spec("tools"));
+    assert!(windows.close("main"));
+    assert!(windows.is_declared("tools"));
+
+    // Windows are not opened until the port runs
+    assert!(!windows.is_open("tools"));
+    assert_eq!(windows.id("tools"), None);
+}
@@ -0,0 +1,168 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    marker::PhantomData,
+};
+
+/// Typed messages shared by the ports of an [`crate::Adapter`], see [`crate::Port::port_with_bus`].
+///
+/// Messages are kept until the end of the next frame, so a message sent during a dispatch can be read by
+/// later ports of the same frame and by every port during the next one. Ports read them with a [`MessageReader`]
+/// to get each message once.
+#[derive(Default)]
+pub struct MessageBus {
+    queues: HashMap<TypeId, Box<dyn AnyQueue>>,
+}
+
+impl MessageBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn send<T: Send + 'static>(&mut self, message: T) {
+        self.queue_mut::<T>().current.push(message);
+    }
+
+    /// Messages of type `T` from the previous and current frame, oldest first. <br/>
+    /// Every call returns all of them, see [`MessageReader`] to only get the messages not read yet.
+    pub fn read<T: Send + 'static>(&self) -> impl Iterator<Item = &T> {
+        self.queue::<T>()
+            .into_iter()
+            .flat_map(|q| q.previous.iter().chain(&q.current))
+    }
+
+    /// Removes and returns the messages of type `T`, oldest first. <br/>
+    /// Use it when a single port handles the messages, e.g. requests.
+    pub fn drain<T: Send + 'static>(&mut self) -> Vec<T> {
+        let queue = self.queue_mut::<T>();
+        queue.first = queue.sent();
+        let mut messages = std::mem::take(&mut queue.previous);
+        messages.append(&mut queue.current);
+        messages
+    }
+
+    pub fn has<T: Send + 'static>(&self) -> bool {
+        self.read::<T>().next().is_some()
+    }
+
+    /// Drops the messages of the previous frame, called by the adapter on each new frame.
+    pub fn next_frame(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.next_frame();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.queues.clear();
+    }
+
+    fn queue<T: Send + 'static>(&self) -> Option<&Queue<T>> {
+        self.queues
+            .get(&TypeId::of::<T>())
+            .and_then(|q| q.as_any().downcast_ref())
+    }
+
+    fn queue_mut<T: Send + 'static>(&mut self) -> &mut Queue<T> {
+        self.queues
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Queue::<T>::default()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("Message queue stored under a mismatched type id")
+    }
+}
+
+/// Cursor over the messages of type `T` of a [`MessageBus`], kept by a port to read each message once. <br/>
+/// Messages dropped before being read, i.e. sent before the previous frame, are skipped.
+pub struct MessageReader<T> {
+    /// Id of the next message to read, counting every message sent on the bus.
+    next: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for MessageReader<T> {
+    fn default() -> Self {
+        Self {
+            next: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for MessageReader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + 'static> MessageReader<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages sent since the previous read, oldest first.
+    pub fn read<'b>(&mut self, bus: &'b MessageBus) -> impl Iterator<Item = &'b T> {
+        let queue = bus.queue::<T>();
+        let skip = queue.map_or(0, |queue| {
+            // Read from the start of a cleared bus
+            let next = match self.next > queue.sent() {
+                true => queue.first,
+                false => self.next.max(queue.first),
+            };
+            self.next = queue.sent();
+            (next - queue.first) as usize
+        });
+        queue
+            .into_iter()
+            .flat_map(|q| q.previous.iter().chain(&q.current))
+            .skip(skip)
+    }
+}
+
+struct Queue<T> {
+    previous: Vec<T>,
+    current: Vec<T>,
+    /// Id of the first message of `previous`, see [`MessageReader`].
+    first: u64,
+}
+
+impl<T> Queue<T> {
+    /// Id of the next message sent.
+    fn sent(&self) -> u64 {
+        self.first + (self.previous.len() + self.current.len()) as u64
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            first: 0,
+        }
+    }
+}
+
+trait AnyQueue: Send {
+    fn next_frame(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + 'static> AnyQueue for Queue<T> {
+    fn next_frame(&mut self) {
+        self.first += self.previous.len() as u64;
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
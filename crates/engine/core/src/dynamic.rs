@@ -0,0 +1,177 @@
+use std::any::type_name_of_val;
+
+use crate::{
+    bus::MessageBus,
+    event::{EventFamily, Propagation},
+    Port,
+};
+
+pub type BoxedPort<F, Inner> = Box<dyn for<'a, 'e> Port<'a, <F as EventFamily>::Event<'e>, Inner>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortId(u64);
+
+struct DynPortEntry<F: EventFamily, Inner> {
+    id: PortId,
+    priority: i32,
+    enabled: bool,
+    name: &'static str,
+    port: BoxedPort<F, Inner>,
+}
+
+/// Port list that can be modified at runtime, unlike port tuples. <br/>
+/// Ports run from the highest to the lowest priority, ports with the same priority run in insertion order.
+///
+/// Events are named through their [`EventFamily`], e.g. `DynPorts<WinPlatform, App>` holds ports of `WinPlatformEvent<'_>`.
+pub struct DynPorts<F: EventFamily, Inner> {
+    entries: Vec<DynPortEntry<F, Inner>>,
+    next_id: u64,
+}
+
+impl<F: EventFamily, Inner> Default for DynPorts<F, Inner> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<F: EventFamily, Inner> DynPorts<F, Inner> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(
+        &mut self,
+        port: impl for<'a, 'e> Port<'a, F::Event<'e>, Inner> + 'static,
+    ) -> PortId {
+        self.insert_with_priority(0, port)
+    }
+
+    pub fn insert_with_priority(
+        &mut self,
+        priority: i32,
+        port: impl for<'a, 'e> Port<'a, F::Event<'e>, Inner> + 'static,
+    ) -> PortId {
+        self.insert_named(priority, type_name_of_val(&port), Box::new(port))
+    }
+
+    pub fn insert_boxed(&mut self, priority: i32, port: BoxedPort<F, Inner>) -> PortId {
+        self.insert_named(priority, "dyn Port", port)
+    }
+
+    /// Inserts a boxed port, `name` identifies it, see [`DynPorts::name`].
+    pub fn insert_named(
+        &mut self,
+        priority: i32,
+        name: &'static str,
+        port: BoxedPort<F, Inner>,
+    ) -> PortId {
+        let id = PortId(self.next_id);
+        self.next_id += 1;
+
+        let index = self.index_for(priority);
+        self.entries.insert(
+            index,
+            DynPortEntry {
+                id,
+                priority,
+                enabled: true,
+                name,
+                port,
+            },
+        );
+        id
+    }
+
+    pub fn remove(&mut self, id: PortId) -> Option<BoxedPort<F, Inner>> {
+        let index = self.position(id)?;
+        Some(self.entries.remove(index).port)
+    }
+
+    pub fn contains(&self, id: PortId) -> bool {
+        self.position(id).is_some()
+    }
+
+    /// Disabled ports are kept in the list but skipped. Returns `false` if the port doesn't exist.
+    pub fn set_enabled(&mut self, id: PortId, enabled: bool) -> bool {
+        let Some(index) = self.position(id) else {
+            return false;
+        };
+        self.entries[index].enabled = enabled;
+        true
+    }
+
+    pub fn is_enabled(&self, id: PortId) -> Option<bool> {
+        self.position(id).map(|i| self.entries[i].enabled)
+    }
+
+    /// Moves the port after every port of the same priority. Returns `false` if the port doesn't exist.
+    pub fn set_priority(&mut self, id: PortId, priority: i32) -> bool {
+        let Some(index) = self.position(id) else {
+            return false;
+        };
+        let mut entry = self.entries.remove(index);
+        entry.priority = priority;
+        let index = self.index_for(priority);
+        self.entries.insert(index, entry);
+        true
+    }
+
+    pub fn priority(&self, id: PortId) -> Option<i32> {
+        self.position(id).map(|i| self.entries[i].priority)
+    }
+
+    /// Name of the port, its type name unless inserted with a name.
+    pub fn name(&self, id: PortId) -> Option<&'static str> {
+        self.position(id).map(|i| self.entries[i].name)
+    }
+
+    /// Ids of the ports in execution order.
+    pub fn ids(&self) -> impl Iterator<Item = PortId> + '_ {
+        self.entries.iter().map(|e| e.id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn position(&self, id: PortId) -> Option<usize> {
+        self.entries.iter().position(|e| e.id == id)
+    }
+
+    fn index_for(&self, priority: i32) -> usize {
+        self.entries.partition_point(|e| e.priority >= priority)
+    }
+}
+
+impl<'a, 'e, F: EventFamily, Inner> Port<'a, F::Event<'e>, Inner> for DynPorts<F, Inner>
+where
+    F::Event<'e>: Propagation,
+{
+    fn port(&mut self, event: &'a mut F::Event<'e>, inner: &mut Inner) {
+        self.port_with_bus(event, inner, &mut MessageBus::new());
+    }
+
+    fn port_with_bus(
+        &mut self,
+        event: &'a mut F::Event<'e>,
+        inner: &mut Inner,
+        bus: &mut MessageBus,
+    ) {
+        for entry in &mut self.entries {
+            if entry.enabled && !(event.is_handled() && entry.port.respects_handled()) {
+                entry.port.port_with_bus(event, inner, bus);
+            }
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors of the fallible engine apis, e.g. `Gpu::try_new`, carrying the underlying error as source.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// No graphics adapter matches the requested backends and options.
+    NoAdapter {
+        backends: String,
+    },
+    RequestDevice {
+        adapter: String,
+        source: BoxError,
+    },
+    CreateSurface {
+        source: BoxError,
+    },
+    /// The surface cannot be presented by the adapter in use, e.g. an integrated gpu surface on a discrete adapter.
+    IncompatibleSurface {
+        adapter: String,
+    },
+    CreateWindow {
+        title: String,
+        source: BoxError,
+    },
+    /// The passes of a render graph cannot be scheduled, e.g. a dependency cycle or a missing pass.
+    RenderGraph {
+        source: BoxError,
+    },
+    EventLoop {
+        source: BoxError,
+    },
+    /// A dynamic library could not be loaded or lacks the expected symbols.
+    Library {
+        path: String,
+        source: BoxError,
+    },
+    /// No window has this id, e.g. it was already removed.
+    UnknownWindow {
+        id: String,
+    },
+    /// The window was created with another pointer type than the requested one, e.g. `Window` instead of `Arc<Window>`.
+    WindowPtrType {
+        expected: String,
+        found: String,
+    },
+    /// Two plugins have the same name.
+    DuplicatePlugin {
+        name: String,
+    },
+    /// A plugin depends on a plugin that was not added.
+    MissingPluginDependency {
+        plugin: String,
+        dependency: String,
+    },
+    /// The plugins depend on each other, directly or transitively.
+    CyclicPlugins {
+        names: Vec<String>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoAdapter { backends } => write!(
+                f,
+                "No graphics adapter found for backends {backends}, check that the graphics drivers are installed"
+            ),
+            Error::RequestDevice { adapter, source } => {
+                write!(f, "Could not acquire graphics device on {adapter}: {source}")
+            }
+            Error::CreateSurface { source } => {
+                write!(f, "Could not create graphics surface: {source}")
+            }
+            Error::IncompatibleSurface { adapter } => {
+                write!(f, "Graphics surface is not supported by {adapter}")
+            }
+            Error::CreateWindow { title, source } => {
+                write!(f, "Could not create window \"{title}\": {source}")
+            }
+            Error::RenderGraph { source } => write!(f, "Failed to build render graph: {source}"),
+            Error::EventLoop { source } => write!(f, "Window event loop failed: {source}"),
+            Error::Library { path, source } => {
+                write!(f, "Could not load library {path}: {source}")
+            }
+            Error::UnknownWindow { id } => write!(f, "No window with id {id}"),
+            Error::WindowPtrType { expected, found } => {
+                write!(f, "Window pointer is a {found}, not a {expected}")
+            }
+            Error::DuplicatePlugin { name } => write!(f, "Plugin {name} was added twice"),
+            Error::MissingPluginDependency { plugin, dependency } => {
+                write!(f, "Plugin {plugin} depends on {dependency} which was not added")
+            }
+            Error::CyclicPlugins { names } => {
+                write!(f, "Cyclic plugin dependencies between {}", names.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::RequestDevice { source, .. }
+            | Error::CreateSurface { source }
+            | Error::CreateWindow { source, .. }
+            | Error::RenderGraph { source }
+            | Error::EventLoop { source }
+            | Error::Library { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -2,9 +2,17 @@
 macro_rules! impl_event_kind {
     ($($name:ident)*) => {
         $(
+          #[derive(Clone)]
           pub struct $name<T> {
               pub inner: T,
-              pub dt: std::time::Duration,
+              pub time: $crate::time::Time,
+          }
+
+          impl<T> $name<T> {
+              /// Scaled time since the previous event of this kind, see [`$crate::time::Time::delta`].
+              pub fn dt(&self) -> std::time::Duration {
+                  self.time.delta
+              }
           }
 
           impl<T> std::ops::Deref for $name<T> {
@@ -20,46 +28,68 @@ macro_rules! impl_event_kind {
                   &mut self.inner
               }
           }
+
+          impl<T> $crate::event::Propagation for $name<T> {}
+
+          impl<T> $crate::event::FrameEvent for $name<T> {}
+
+          impl<T> $crate::event::EventFamily for $name<T> {
+              type Event<'e> = $name<T>;
+          }
         )*
     };
 }
 
 impl_event_kind!(Render Update);
 
-#[macro_export]
-macro_rules! impl_schema {
-    ($name:ident := $first:ident $(+ $kind:ident)*) => {
-        #[allow(non_snake_case)]
-        pub struct $name<$first, $($kind,)* Inner> {
-            $first: $first,
-            $($kind: $kind,)*
-            inner: Inner
-        }
-
-        #[allow(non_snake_case)]
-        impl<$first, $($kind,)* Inner> $name<$first, $($kind,)* Inner> {
-            pub fn new(inner: Inner, $first: $first, $($kind: $kind,)*) -> Self {
-                Self {
-                    inner, $first, $($kind,)*
-                }
-            }
-        }
-
-        $crate::impl_schema!(@foreach $name $first $($kind)* : ($first $($kind)*));
-    };
-    (@foreach $name:ident $($spe:ident)* : $kinds:tt) => {
-        $(
-            $crate::impl_schema!(@solo $name $spe $kinds);
-        )*
-    };
-    (@solo $name:ident $spe:ident ($($kind:ident)*)) => {
-        impl<$($kind,)* Inner, Event> $crate::Controller<self::$spe<Event>> for $name<$($kind,)* Inner>
-        where
-            $spe: for<'a> $crate::Port<'a, self::$spe<Event>, Inner>,
-        {
-            fn run(&mut self, mut event: self::$spe<Event>) {
-                self.$spe.port(&mut event, &mut self.inner);
-            }
-        }
+/// Events that can be marked as handled by a port, so that later ports respecting the flag skip them. <br/>
+/// Events that cannot be consumed keep the default implementation, marking them as handled does nothing.
+///
+/// Required by port tuples, custom event types opt in with an empty implementation:
+///
+/// ```
+/// struct MyEvent;
+///
+/// impl nuum_core::event::Propagation for MyEvent {}
+/// impl nuum_core::event::FrameEvent for MyEvent {}
+/// ```
+pub trait Propagation {
+    fn is_handled(&self) -> bool {
+        false
     }
+
+    fn set_handled(&mut self) {}
+}
+
+impl Propagation for () {}
+
+/// Events starting a new frame, where an [`crate::Adapter`] drops the messages of the previous one. <br/>
+/// Platform events start a frame on update. Required by [`crate::Adapter`], other custom event types opt in with an
+/// empty implementation, see [`Propagation`].
+pub trait FrameEvent {
+    fn is_new_frame(&self) -> bool {
+        false
+    }
+}
+
+impl FrameEvent for () {}
+
+impl<E: crate::platform::PlatformEvent> FrameEvent for E {
+    fn is_new_frame(&self) -> bool {
+        self.is_update()
+    }
+}
+
+/// Names an event type for every lifetime at once, e.g. every `WinPlatformEvent<'e>` through `WinPlatform`. <br/>
+/// Needed to store ports of events borrowing from the platform, see [`crate::dynamic::DynPorts`].
+pub trait EventFamily {
+    type Event<'e>;
+}
+
+impl<P: crate::platform::Platform> EventFamily for P {
+    type Event<'e> = P::Event<'e>;
+}
+
+impl EventFamily for () {
+    type Event<'e> = ();
 }
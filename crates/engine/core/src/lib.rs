@@ -1,26 +1,83 @@
+pub mod bus;
+pub mod dynamic;
+pub mod error;
 pub mod event;
+pub mod parallel;
 pub mod platform;
+pub mod profile;
+pub mod state;
+pub mod time;
 pub use mint as maths;
+pub use nuum_macros::{Controller, Schema};
+
+use bus::MessageBus;
 
 pub trait Controller<Event> {
     fn run(&mut self, input: Event);
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a port of `{Event}` events with `{Inner}` as inner",
+    label = "missing `Port<'_, {Event}, {Inner}>` implementation"
+)]
 pub trait Port<'a, Event, Inner> {
     fn port(&mut self, event: &'a mut Event, inner: &mut Inner);
+
+    /// Same as [`Port::port`], with the message bus of the [`Adapter`] or `#[derive(Schema)]` running the port. <br/>
+    /// Ports using messages override it. Their `port` is only called when running the port directly, and runs it with
+    /// a bus scoped to the call, e.g. `&mut MessageBus::new()`, so messages only reach the later ports of the call.
+    fn port_with_bus(&mut self, event: &'a mut Event, inner: &mut Inner, bus: &mut MessageBus) {
+        let _ = bus;
+        self.port(event, inner);
+    }
+
+    /// Whether this port is skipped when a previous port marked the event as handled, see [`event::Propagation`].
+    fn respects_handled(&self) -> bool {
+        true
+    }
+}
+
+/// Shorthand for `for<'a> Port<'a, Event, Inner>`, used by derive macros for better compile errors.
+pub trait PortFor<Event, Inner> {
+    /// Runs the port with [`Port::port_with_bus`].
+    fn port_for(&mut self, event: &mut Event, inner: &mut Inner, bus: &mut MessageBus);
+}
+
+impl<Event, Inner, P: for<'a> Port<'a, Event, Inner>> PortFor<Event, Inner> for P {
+    fn port_for(&mut self, event: &mut Event, inner: &mut Inner, bus: &mut MessageBus) {
+        self.port_with_bus(event, inner, bus);
+    }
 }
 
 pub struct Adapter<Port, Inner> {
     pub ports: Port,
     pub inner: Inner,
+    pub bus: MessageBus,
+}
+
+impl<P, I> Adapter<P, I> {
+    pub fn new(ports: P, inner: I) -> Self {
+        Self {
+            ports,
+            inner,
+            bus: MessageBus::new(),
+        }
+    }
 }
 
 impl<P, I, E> Controller<E> for Adapter<P, I>
 where
     P: for<'a> Port<'a, E, I>,
+    E: event::FrameEvent,
 {
+    /// Ports are timed as a whole by the [`profile`]r, under the type name of `P`.
     fn run(&mut self, mut input: E) {
-        self.ports.port(&mut input, &mut self.inner);
+        let _scope = profile::scope(std::any::type_name::<P>());
+        if input.is_new_frame() {
+            self.bus.next_frame();
+        }
+        self.ports
+            .port_with_bus(&mut input, &mut self.inner, &mut self.bus);
     }
 }
 
@@ -42,15 +99,26 @@ impl<Event> Controller<Event> for () {
 
 macro_rules! impl_port_tuples {
     ($($t:ident $l:lifetime),*) => {
-        impl<'_a, _E, _I, $( $t: for<'a> Port<'a, _E, _I> ),*> Port<'_a, _E, _I> for ( $( $t, )* )
+        impl<'_a, _E: event::Propagation, _I, $( $t: for<'a> Port<'a, _E, _I> ),*> Port<'_a, _E, _I> for ( $( $t, )* )
         where  {
-            fn port(& mut self, mut _event: & mut _E, _inner: & mut _I) {
+            fn port(& mut self, _event: & mut _E, _inner: & mut _I) {
+                self.port_with_bus(_event, _inner, &mut MessageBus::new());
+            }
+
+            fn port_with_bus(& mut self, _event: & mut _E, _inner: & mut _I, _bus: &mut MessageBus) {
                 #[allow(non_snake_case)]
                 let ($($t,)*) = self;
                 $(
-                    $t.port(_event, _inner);
+                    if !(event::Propagation::is_handled(_event) && $t.respects_handled()) {
+                        $t.port_with_bus(_event, _inner, _bus);
+                    }
                 )*
             }
+
+            // Each port checks the flag itself, so that non-respecting ports of a nested tuple still run
+            fn respects_handled(&self) -> bool {
+                false
+            }
         }
     };
 }
@@ -0,0 +1,166 @@
+use std::any::{type_name, TypeId};
+
+use crate::Port;
+
+/// Parts of the inner controller read or written by a [`ParallelPort`], identified by type. <br/>
+/// The access is advisory: it only drives the scheduling of [`ParallelPorts`], ports still receive the whole inner
+/// controller and nothing checks that they stay within their declaration. A port touching undeclared parts stays
+/// memory safe, the inner controller being `Sync`, but may contend on locks with the ports running alongside it.
+#[derive(Debug, Clone, Default)]
+pub struct Access {
+    reads: Vec<(TypeId, &'static str)>,
+    writes: Vec<(TypeId, &'static str)>,
+    exclusive: bool,
+}
+
+impl Access {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Conflicts with every other port, for ports touching the whole inner controller.
+    pub fn exclusive() -> Self {
+        Self {
+            exclusive: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn read<T: ?Sized + 'static>(mut self) -> Self {
+        self.reads.push((TypeId::of::<T>(), type_name::<T>()));
+        self
+    }
+
+    pub fn write<T: ?Sized + 'static>(mut self) -> Self {
+        self.writes.push((TypeId::of::<T>(), type_name::<T>()));
+        self
+    }
+
+    /// Whether two ports cannot run at the same time, i.e. one writes a part the other accesses.
+    pub fn conflicts(&self, other: &Access) -> bool {
+        let overlaps = |a: &[(TypeId, &str)], b: &[(TypeId, &str)]| {
+            a.iter().any(|(x, _)| b.iter().any(|(y, _)| x == y))
+        };
+        self.exclusive
+            || other.exclusive
+            || overlaps(&self.writes, &other.writes)
+            || overlaps(&self.writes, &other.reads)
+            || overlaps(&self.reads, &other.writes)
+    }
+}
+
+/// Port run on a thread pool by [`ParallelPorts`], with shared access to the event and the inner controller. <br/>
+/// Written parts are usually behind a `Mutex` or `RwLock` in the inner controller, locks are never contended since
+/// conflicting ports never overlap, as long as the port keeps to its declared [`Access`].
+pub trait ParallelPort<Event, Inner>: Send {
+    fn access(&self) -> Access;
+    fn port(&mut self, event: &Event, inner: &Inner);
+}
+
+impl<Event, Inner, F: FnMut(&Event, &Inner) + Send> ParallelPort<Event, Inner> for (Access, F) {
+    fn access(&self) -> Access {
+        self.0.clone()
+    }
+
+    fn port(&mut self, event: &Event, inner: &Inner) {
+        (self.1)(event, inner);
+    }
+}
+
+/// Runs ports with disjoint [`Access`] concurrently on the rayon thread pool.
+///
+/// Ports are grouped into batches run one after another, a port runs after every conflicting port inserted before
+/// it. Meant for update work, e.g. as the update port of a nested [`crate::Adapter`], ports needing the main thread
+/// such as rendering stay regular ports.
+pub struct ParallelPorts<Event, Inner> {
+    ports: Vec<Box<dyn ParallelPort<Event, Inner>>>,
+    batches: Vec<Vec<usize>>,
+    /// Batch of each port, by port index.
+    batch_of: Vec<usize>,
+}
+
+impl<Event, Inner> Default for ParallelPorts<Event, Inner> {
+    fn default() -> Self {
+        Self {
+            ports: Vec::new(),
+            batches: Vec::new(),
+            batch_of: Vec::new(),
+        }
+    }
+}
+
+impl<Event, Inner> ParallelPorts<Event, Inner> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, port: impl ParallelPort<Event, Inner> + 'static) -> Self {
+        self.insert(port);
+        self
+    }
+
+    pub fn insert(&mut self, port: impl ParallelPort<Event, Inner> + 'static) {
+        self.ports.push(Box::new(port));
+        self.batches.clear();
+        self.batch_of.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.ports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ports.is_empty()
+    }
+
+    /// Indices of the ports run together, in execution order.
+    pub fn batches(&mut self) -> &[Vec<usize>] {
+        if self.batches.is_empty() && !self.ports.is_empty() {
+            self.schedule();
+        }
+        &self.batches
+    }
+
+    fn schedule(&mut self) {
+        let accesses: Vec<Access> = self.ports.iter().map(|p| p.access()).collect();
+        for (i, access) in accesses.iter().enumerate() {
+            let batch = (0..i)
+                .filter(|&j| access.conflicts(&accesses[j]))
+                .map(|j| self.batch_of[j] + 1)
+                .max()
+                .unwrap_or(0);
+            if batch == self.batches.len() {
+                self.batches.push(Vec::new());
+            }
+            self.batches[batch].push(i);
+            self.batch_of.push(batch);
+        }
+    }
+}
+
+impl<Event: Sync, Inner: Sync> ParallelPorts<Event, Inner> {
+    pub fn run(&mut self, event: &Event, inner: &Inner) {
+        self.batches();
+        let mut batches: Vec<Vec<_>> = self.batches.iter().map(|_| Vec::new()).collect();
+        for (port, &batch) in self.ports.iter_mut().zip(&self.batch_of) {
+            batches[batch].push(port);
+        }
+
+        for batch in batches {
+            let mut ports = batch.into_iter();
+            let Some(first) = ports.next() else { continue };
+            rayon::scope(|scope| {
+                for port in ports {
+                    scope.spawn(move |_| port.port(event, inner));
+                }
+                first.port(event, inner);
+            });
+        }
+    }
+}
+
+impl<'a, Event: Sync, Inner: Sync> Port<'a, Event, Inner> for ParallelPorts<Event, Inner> {
+    fn port(&mut self, event: &'a mut Event, inner: &mut Inner) {
+        self.run(event, inner);
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::Controller;
 
 pub trait Platform {
@@ -9,4 +11,10 @@ pub trait Platform {
 pub trait PlatformEvent {
     fn exit(&self);
     fn is_update(&self) -> bool;
+
+    /// Time elapsed since the previous event of the same kind, when the platform drives time itself (e.g. replays). <br/>
+    /// Ports should measure time themselves when `None`.
+    fn dt(&self) -> Option<Duration> {
+        None
+    }
 }
@@ -0,0 +1,192 @@
+//! Optional cpu profiler, timing ports and render passes once enabled.
+//!
+//! Scopes are aggregated per name over the last [`WINDOW`] samples, see [`stats`]. Disabled by default, a disabled
+//! scope costs a single atomic load.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Number of samples kept per scope for the rolling statistics.
+pub const WINDOW: usize = 240;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default)]
+struct Samples {
+    count: u64,
+    recent: VecDeque<Duration>,
+}
+
+fn registry() -> MutexGuard<'static, HashMap<String, Samples>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<String, Samples>>> = OnceLock::new();
+    REGISTRY
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn enable(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Times until dropped, recorded under `name`.
+pub fn scope(name: &str) -> Scope<'_> {
+    Scope {
+        name,
+        start: is_enabled().then(Instant::now),
+    }
+}
+
+pub fn record(name: &str, duration: Duration) {
+    let mut registry = registry();
+    let samples = match registry.get_mut(name) {
+        Some(samples) => samples,
+        None => registry.entry(name.to_owned()).or_default(),
+    };
+    samples.count += 1;
+    if samples.recent.len() == WINDOW {
+        samples.recent.pop_front();
+    }
+    samples.recent.push_back(duration);
+}
+
+pub fn reset() {
+    registry().clear();
+}
+
+pub struct Scope<'a> {
+    name: &'a str,
+    start: Option<Instant>,
+}
+
+impl Drop for Scope<'_> {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(self.name, start.elapsed());
+        }
+    }
+}
+
+/// Rolling statistics of a scope over its recent samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeStats {
+    pub name: String,
+    /// Samples recorded since the last reset, including those out of the window.
+    pub count: u64,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+}
+
+/// Statistics of every recorded scope, sorted by name.
+pub fn stats() -> Vec<ScopeStats> {
+    let registry = registry();
+    let mut stats: Vec<ScopeStats> = registry
+        .iter()
+        .filter(|(_, samples)| !samples.recent.is_empty())
+        .map(|(name, samples)| {
+            let mut sorted: Vec<Duration> = samples.recent.iter().copied().collect();
+            sorted.sort_unstable();
+            let percentile = |p: usize| sorted[(sorted.len() - 1) * p / 100];
+            ScopeStats {
+                name: name.clone(),
+                count: samples.count,
+                min: sorted[0],
+                avg: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+                max: sorted[sorted.len() - 1],
+                p50: percentile(50),
+                p95: percentile(95),
+                p99: percentile(99),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| a.name.cmp(&b.name));
+    stats
+}
+
+/// Durations in microseconds.
+pub fn to_csv(stats: &[ScopeStats]) -> String {
+    let mut csv = String::from("name,count,min_us,avg_us,max_us,p50_us,p95_us,p99_us\n");
+    for s in stats {
+        let _ = writeln!(
+            csv,
+            "\"{}\",{},{},{},{},{},{},{}",
+            s.name.replace('"', "\"\""),
+            s.count,
+            s.min.as_micros(),
+            s.avg.as_micros(),
+            s.max.as_micros(),
+            s.p50.as_micros(),
+            s.p95.as_micros(),
+            s.p99.as_micros()
+        );
+    }
+    csv
+}
+
+/// Durations in microseconds.
+pub fn to_json(stats: &[ScopeStats]) -> String {
+    let mut json = String::from("[");
+    for (i, s) in stats.iter().enumerate() {
+        let name = s.name.replace('\\', "\\\\").replace('"', "\\\"");
+        let _ = write!(
+            json,
+            "{}\n  {{\"name\": \"{name}\", \"count\": {}, \"min_us\": {}, \"avg_us\": {}, \"max_us\": {}, \"p50_us\": {}, \"p95_us\": {}, \"p99_us\": {}}}",
+            if i == 0 { "" } else { "," },
+            s.count,
+            s.min.as_micros(),
+            s.avg.as_micros(),
+            s.max.as_micros(),
+            s.p50.as_micros(),
+            s.p95.as_micros(),
+            s.p99.as_micros()
+        );
+    }
+    json.push_str("\n]\n");
+    json
+}
+
+/// Writes the current statistics, as json if the path ends with `.json`, csv otherwise.
+pub fn dump(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    let stats = stats();
+    let content = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => to_json(&stats),
+        _ => to_csv(&stats),
+    };
+    fs::write(path, content)
+}
+
+/// Enables the profiler and dumps the statistics to `path` when dropped, e.g. held in `main` until exit.
+pub struct DumpOnDrop(PathBuf);
+
+impl DumpOnDrop {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        enable(true);
+        Self(path.into())
+    }
+}
+
+impl Drop for DumpOnDrop {
+    fn drop(&mut self) {
+        if let Err(e) = dump(&self.0) {
+            tracing::warn!(path = %self.0.display(), "Failed to write profile: {e}");
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crate::{
+    bus::MessageBus,
+    dynamic::{DynPorts, PortId},
+    event::{EventFamily, Propagation},
+    Controller, Port,
+};
+
+/// Sent to the inner controller when a state becomes part of the stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnEnter<S>(pub S);
+
+/// Sent to the inner controller when a state leaves the stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnExit<S>(pub S);
+
+#[derive(Debug, Clone)]
+enum Transition<S> {
+    Set(S),
+    Push(S),
+    Pop,
+}
+
+struct StatesShared<S> {
+    stack: Vec<S>,
+    pending: Vec<Transition<S>>,
+    overlays: Vec<S>,
+}
+
+impl<S: Clone + PartialEq> StatesShared<S> {
+    /// Active states, from the top of the stack.
+    fn active(&self) -> Vec<S> {
+        let mut active = Vec::new();
+        for state in self.stack.iter().rev() {
+            active.push(state.clone());
+            if !self.overlays.contains(state) {
+                break;
+            }
+        }
+        active
+    }
+}
+
+/// Shared handle to the state stack of a [`StateMachine`], to query or request transitions from anywhere. <br/>
+/// Transitions are applied by the state machine at the start of its next event, in request order.
+pub struct States<S>(Arc<Mutex<StatesShared<S>>>);
+
+impl<S> Clone for States<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S: Clone + PartialEq> States<S> {
+    /// State on top of the stack.
+    pub fn current(&self) -> Option<S> {
+        self.lock().stack.last().cloned()
+    }
+
+    pub fn is_current(&self, state: &S) -> bool {
+        self.lock().stack.last() == Some(state)
+    }
+
+    /// Whether the state is anywhere in the stack, e.g. covered by an overlay.
+    pub fn contains(&self, state: &S) -> bool {
+        self.lock().stack.contains(state)
+    }
+
+    /// Whether the ports of the state run, see [`StateMachine`]: the state on top of the stack, and the states
+    /// below it as long as the states above them are overlays.
+    pub fn is_active(&self, state: &S) -> bool {
+        self.lock().active().contains(state)
+    }
+
+    /// Bottom to top.
+    pub fn stack(&self) -> Vec<S> {
+        self.lock().stack.clone()
+    }
+
+    /// Replaces the current state.
+    pub fn set(&self, state: S) {
+        self.lock().pending.push(Transition::Set(state));
+    }
+
+    /// Enters a state on top of the current one, which is entered again on [`States::pop`] without events.
+    pub fn push(&self, state: S) {
+        self.lock().pending.push(Transition::Push(state));
+    }
+
+    /// Leaves the current state for the one below, the last state of the stack is never popped, see [`States::set`].
+    pub fn pop(&self) {
+        self.lock().pending.push(Transition::Pop);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, StatesShared<S>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Port running the ports of the active states, see [`States`] to switch states.
+///
+/// Only the ports of the state on top of the stack run, unless it is an overlay, in which case the state below
+/// runs too, e.g. to keep rendering the game behind a pause menu. Active states run from top to bottom, so that
+/// overlays can handle input first.
+pub struct StateMachine<S, F: EventFamily, Inner> {
+    states: States<S>,
+    ports: HashMap<S, DynPorts<F, Inner>>,
+}
+
+impl<S: Clone + Eq + Hash, F: EventFamily, Inner> StateMachine<S, F, Inner> {
+    /// `initial` is entered on the first event.
+    pub fn new(initial: S) -> Self {
+        Self {
+            states: States(Arc::new(Mutex::new(StatesShared {
+                stack: Vec::new(),
+                pending: vec![Transition::Push(initial)],
+                overlays: Vec::new(),
+            }))),
+            ports: HashMap::new(),
+        }
+    }
+
+    pub fn states(&self) -> States<S> {
+        self.states.clone()
+    }
+
+    pub fn with_port(
+        mut self,
+        state: S,
+        port: impl for<'a, 'e> Port<'a, F::Event<'e>, Inner> + 'static,
+    ) -> Self {
+        self.insert_port(state, port);
+        self
+    }
+
+    pub fn with_overlay(mut self, state: S) -> Self {
+        self.set_overlay(state, true);
+        self
+    }
+
+    pub fn insert_port(
+        &mut self,
+        state: S,
+        port: impl for<'a, 'e> Port<'a, F::Event<'e>, Inner> + 'static,
+    ) -> PortId {
+        self.ports_mut(state).insert(port)
+    }
+
+    /// Ports of a state, to add or remove ports at runtime.
+    pub fn ports_mut(&mut self, state: S) -> &mut DynPorts<F, Inner> {
+        self.ports.entry(state).or_default()
+    }
+
+    pub fn set_overlay(&mut self, state: S, overlay: bool) {
+        let overlays = &mut self.states.lock().overlays;
+        overlays.retain(|s| *s != state);
+        if overlay {
+            overlays.push(state);
+        }
+    }
+
+    /// Applies pending transitions, delivering their events to `inner`.
+    pub fn apply(&mut self, inner: &mut Inner)
+    where
+        Inner: Controller<OnEnter<S>> + Controller<OnExit<S>>,
+    {
+        loop {
+            // Released before running the events, so that controllers can request transitions
+            let transition = {
+                let mut shared = self.states.lock();
+                if shared.pending.is_empty() {
+                    return;
+                }
+                let transition = shared.pending.remove(0);
+                let exited = match transition {
+                    Transition::Set(_) => shared.stack.pop(),
+                    Transition::Pop if shared.stack.len() > 1 => shared.stack.pop(),
+                    Transition::Pop => {
+                        tracing::warn!("Ignoring pop of the last state");
+                        None
+                    }
+                    Transition::Push(_) => None,
+                };
+                if let Transition::Set(state) | Transition::Push(state) = &transition {
+                    shared.stack.push(state.clone());
+                }
+                (exited, transition)
+            };
+
+            match transition {
+                (exited, Transition::Set(state) | Transition::Push(state)) => {
+                    if let Some(exited) = exited {
+                        inner.run(OnExit(exited));
+                    }
+                    inner.run(OnEnter(state));
+                }
+                (Some(exited), Transition::Pop) => inner.run(OnExit(exited)),
+                (None, Transition::Pop) => (),
+            }
+        }
+    }
+
+    fn active(&self) -> Vec<S> {
+        self.states.lock().active()
+    }
+}
+
+impl<'a, 'e, S, F, Inner> Port<'a, F::Event<'e>, Inner> for StateMachine<S, F, Inner>
+where
+    S: Clone + Eq + Hash,
+    F: EventFamily,
+    F::Event<'e>: Propagation,
+    Inner: Controller<OnEnter<S>> + Controller<OnExit<S>>,
+{
+    fn port(&mut self, event: &'a mut F::Event<'e>, inner: &mut Inner) {
+        self.port_with_bus(event, inner, &mut MessageBus::new());
+    }
+
+    fn port_with_bus(
+        &mut self,
+        event: &'a mut F::Event<'e>,
+        inner: &mut Inner,
+        bus: &mut MessageBus,
+    ) {
+        self.apply(inner);
+        for state in self.active() {
+            if let Some(ports) = self.ports.get_mut(&state) {
+                ports.port_with_bus(event, inner, bus);
+            }
+        }
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// Snapshot of a [`Clock`], carried by events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Time {
+    /// Total scaled time, stops while paused.
+    pub elapsed: Duration,
+    /// Total real time, ignoring pause and scale.
+    pub unscaled_elapsed: Duration,
+    /// Number of ticks of the clock, including the current one.
+    pub frame: u64,
+    /// Scaled time since the previous tick, zero while paused.
+    pub delta: Duration,
+    /// Real time since the previous tick.
+    pub unscaled_delta: Duration,
+    pub paused: bool,
+    pub scale: f32,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            unscaled_elapsed: Duration::ZERO,
+            frame: 0,
+            delta: Duration::ZERO,
+            unscaled_delta: Duration::ZERO,
+            paused: false,
+            scale: 1.,
+        }
+    }
+}
+
+impl Time {
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    fn advance(&mut self, unscaled_delta: Duration) {
+        self.frame += 1;
+        self.unscaled_delta = unscaled_delta;
+        self.unscaled_elapsed = self.unscaled_elapsed.saturating_add(unscaled_delta);
+        // Scaling through a float rounds the delta, keep it exact for deterministic replays
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else if self.scale == 1. {
+            unscaled_delta
+        } else {
+            // Same as `mul_f32`, saturating instead of panicking on overflow
+            Duration::try_from_secs_f32(unscaled_delta.as_secs_f32() * self.scale)
+                .unwrap_or(Duration::MAX)
+        };
+        self.elapsed = self.elapsed.saturating_add(self.delta);
+    }
+}
+
+/// Shared handle to a [`Time`], advanced by the ports producing events. <br/>
+/// Clones control the same clock, keep one to pause or scale time from a controller.
+///
+/// A clock must only be advanced by a single port: giving clones to several drivers, e.g. an `EventLoopPort` and a
+/// `RenderPort`, advances it once per driver and counts the elapsed time several times. Give each driver its own
+/// clock and keep a clone of each to control them.
+#[derive(Debug, Clone, Default)]
+pub struct Clock(Arc<Mutex<Time>>);
+
+impl Clock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn time(&self) -> Time {
+        *self.lock()
+    }
+
+    /// Advances the clock by `unscaled_delta` of real time, returns the new snapshot.
+    pub fn advance(&self, unscaled_delta: Duration) -> Time {
+        let mut time = self.lock();
+        time.advance(unscaled_delta);
+        *time
+    }
+
+    pub fn pause(&self) {
+        self.set_paused(true);
+    }
+
+    pub fn resume(&self) {
+        self.set_paused(false);
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.lock().paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.lock().paused
+    }
+
+    /// Multiplier applied to real time, e.g. `0.5` for slow motion. <br/>
+    /// Clamped to a finite positive value, `NaN` stops the time like a zero scale.
+    pub fn set_scale(&self, scale: f32) {
+        let scale = if scale.is_nan() { 0. } else { scale };
+        self.lock().scale = scale.clamp(0., f32::MAX);
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.lock().scale
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Time> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,128 @@
+use nuum_core::{
+    bus::{MessageBus, MessageReader},
+    event::{FrameEvent, Propagation},
+    Adapter, Controller, Port,
+};
+
+#[test]
+fn messages_are_kept_until_the_end_of_the_next_frame() {
+    let mut bus = MessageBus::new();
+    bus.send(1u32);
+    assert_eq!(bus.read::<u32>().collect::<Vec<_>>(), [&1]);
+
+    bus.next_frame();
+    bus.send(2u32);
+    assert_eq!(bus.read::<u32>().collect::<Vec<_>>(), [&1, &2]);
+
+    bus.next_frame();
+    assert_eq!(bus.read::<u32>().collect::<Vec<_>>(), [&2]);
+
+    bus.next_frame();
+    assert!(!bus.has::<u32>());
+}
+
+#[test]
+fn messages_are_queued_per_type() {
+    let mut bus = MessageBus::new();
+    bus.send(1u32);
+    bus.send("text");
+
+    assert_eq!(bus.drain::<u32>(), [1]);
+    assert!(!bus.has::<u32>());
+    assert!(bus.has::<&str>());
+}
+
+#[test]
+fn readers_get_each_message_once() {
+    let mut bus = MessageBus::new();
+    let mut reader = MessageReader::<u32>::new();
+    assert_eq!(reader.read(&bus).count(), 0);
+
+    bus.send(1u32);
+    bus.send(2u32);
+    assert_eq!(reader.read(&bus).collect::<Vec<_>>(), [&1, &2]);
+    assert_eq!(reader.read(&bus).count(), 0);
+
+    bus.next_frame();
+    bus.send(3u32);
+    assert_eq!(reader.read(&bus).collect::<Vec<_>>(), [&3]);
+}
+
+#[test]
+fn readers_skip_dropped_messages() {
+    let mut bus = MessageBus::new();
+    let mut late = MessageReader::<u32>::new();
+    let mut drained = MessageReader::<u32>::new();
+    bus.send(1u32);
+    bus.next_frame();
+    bus.send(2u32);
+    bus.next_frame();
+    bus.send(3u32);
+    assert_eq!(late.read(&bus).collect::<Vec<_>>(), [&2, &3]);
+
+    assert_eq!(drained.read(&bus).count(), 2);
+    bus.send(4u32);
+    assert_eq!(bus.drain::<u32>(), [2, 3, 4]);
+    bus.send(5u32);
+    assert_eq!(drained.read(&bus).collect::<Vec<_>>(), [&5]);
+}
+
+#[test]
+fn drain_takes_both_frames() {
+    let mut bus = MessageBus::new();
+    bus.send(1u32);
+    bus.next_frame();
+    bus.send(2u32);
+
+    assert_eq!(bus.drain::<u32>(), [1, 2]);
+    bus.next_frame();
+    assert!(!bus.has::<u32>());
+}
+
+struct Frame {
+    new: bool,
+}
+
+impl Propagation for Frame {}
+
+impl FrameEvent for Frame {
+    fn is_new_frame(&self) -> bool {
+        self.new
+    }
+}
+
+struct Sender(u32);
+
+impl<'a> Port<'a, Frame, ()> for Sender {
+    fn port(&mut self, event: &mut Frame, inner: &mut ()) {
+        self.port_with_bus(event, inner, &mut MessageBus::new());
+    }
+
+    fn port_with_bus(&mut self, _: &mut Frame, _: &mut (), bus: &mut MessageBus) {
+        self.0 += 1;
+        bus.send(self.0);
+    }
+}
+
+#[derive(Default)]
+struct Reader(MessageReader<u32>, Vec<Vec<u32>>);
+
+impl<'a> Port<'a, Frame, ()> for Reader {
+    fn port(&mut self, event: &mut Frame, inner: &mut ()) {
+        self.port_with_bus(event, inner, &mut MessageBus::new());
+    }
+
+    fn port_with_bus(&mut self, _: &mut Frame, _: &mut (), bus: &mut MessageBus) {
+        self.1.push(self.0.read(bus).copied().collect());
+    }
+}
+
+#[test]
+fn adapter_bus_is_read_once_per_message() {
+    let mut app = Adapter::new((Sender(0), Reader::default()), ());
+    for new in [true, false, true, true] {
+        app.run(Frame { new });
+    }
+
+    assert_eq!(app.ports.1 .1, [[1], [2], [3], [4]]);
+}
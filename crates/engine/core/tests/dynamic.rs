@@ -0,0 +1,70 @@
+use nuum_core::{dynamic::DynPorts, Port};
+
+type Log = Vec<&'static str>;
+
+fn log(name: &'static str) -> impl FnMut(&mut (), &mut Log) {
+    move |_, log| log.push(name)
+}
+
+fn run(ports: &mut DynPorts<(), Log>) -> Log {
+    let mut log = Log::new();
+    ports.port(&mut (), &mut log);
+    log
+}
+
+#[test]
+fn ports_run_by_priority_then_insertion_order() {
+    let mut ports = DynPorts::<(), Log>::new();
+    ports.insert(log("a"));
+    ports.insert_with_priority(10, log("high"));
+    ports.insert(log("b"));
+    ports.insert_with_priority(-10, log("low"));
+    ports.insert_with_priority(10, log("high 2"));
+
+    assert_eq!(run(&mut ports), ["high", "high 2", "a", "b", "low"]);
+}
+
+#[test]
+fn set_priority_moves_after_the_same_priority() {
+    let mut ports = DynPorts::<(), Log>::new();
+    let a = ports.insert(log("a"));
+    ports.insert_with_priority(1, log("b"));
+    ports.insert_with_priority(1, log("c"));
+
+    assert!(ports.set_priority(a, 1));
+    assert_eq!(ports.priority(a), Some(1));
+    assert_eq!(run(&mut ports), ["b", "c", "a"]);
+}
+
+#[test]
+fn removed_ports_no_longer_run() {
+    let mut ports = DynPorts::<(), Log>::new();
+    let a = ports.insert(log("a"));
+    let b = ports.insert(log("b"));
+
+    assert!(ports.remove(a).is_some());
+    assert!(ports.remove(a).is_none());
+    assert!(!ports.contains(a));
+    assert!(!ports.set_priority(a, 1));
+    assert_eq!(ports.ids().collect::<Vec<_>>(), [b]);
+    assert_eq!(run(&mut ports), ["b"]);
+
+    // Ids are not reused
+    let c = ports.insert(log("c"));
+    assert_ne!(c, a);
+    assert_eq!(ports.len(), 2);
+}
+
+#[test]
+fn disabled_ports_are_skipped() {
+    let mut ports = DynPorts::<(), Log>::new();
+    let a = ports.insert(log("a"));
+    ports.insert(log("b"));
+
+    assert!(ports.set_enabled(a, false));
+    assert_eq!(ports.is_enabled(a), Some(false));
+    assert_eq!(run(&mut ports), ["b"]);
+
+    ports.set_enabled(a, true);
+    assert_eq!(run(&mut ports), ["a", "b"]);
+}
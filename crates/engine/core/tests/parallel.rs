@@ -0,0 +1,86 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Mutex,
+};
+
+use nuum_core::parallel::{Access, ParallelPorts};
+
+#[derive(Default)]
+struct World {
+    position: AtomicU32,
+    velocity: AtomicU32,
+    log: Mutex<Vec<&'static str>>,
+}
+
+struct Position;
+struct Velocity;
+
+fn noop(access: Access) -> (Access, impl FnMut(&(), &World) + Send) {
+    (access, |_: &(), _: &World| ())
+}
+
+#[test]
+fn conflicting_writes_split_batches() {
+    let mut ports = ParallelPorts::<(), World>::new()
+        .with(noop(Access::new().write::<Position>()))
+        .with(noop(Access::new().read::<Position>()))
+        .with(noop(Access::new().write::<Velocity>()))
+        .with(noop(Access::new().write::<Velocity>()))
+        .with(noop(Access::new()));
+
+    assert_eq!(ports.batches(), [vec![0, 2, 4], vec![1, 3]]);
+}
+
+#[test]
+fn readers_share_a_batch() {
+    let mut ports = ParallelPorts::<(), World>::new()
+        .with(noop(Access::new().read::<Position>()))
+        .with(noop(Access::new().read::<Position>().read::<Velocity>()))
+        .with(noop(Access::new().read::<Velocity>()));
+
+    assert_eq!(ports.batches(), [vec![0, 1, 2]]);
+}
+
+#[test]
+fn exclusive_ports_run_alone() {
+    let mut ports = ParallelPorts::<(), World>::new()
+        .with(noop(Access::new()))
+        .with(noop(Access::exclusive()))
+        .with(noop(Access::new()));
+
+    assert_eq!(ports.batches(), [vec![0], vec![1], vec![2]]);
+
+    // Batches are scheduled again after an insertion
+    ports.insert(noop(Access::new()));
+    assert_eq!(ports.batches(), [vec![0], vec![1], vec![2, 3]]);
+}
+
+#[test]
+fn conflicting_ports_run_in_insertion_order() {
+    let mut ports = ParallelPorts::<(), World>::new()
+        .with((
+            Access::new().write::<Position>(),
+            |_: &(), world: &World| {
+                world.position.fetch_add(1, Ordering::Relaxed);
+            },
+        ))
+        .with((
+            Access::new().read::<Position>().write::<Velocity>(),
+            |_: &(), world: &World| {
+                let position = world.position.load(Ordering::Relaxed);
+                world.velocity.store(position * 10, Ordering::Relaxed);
+            },
+        ))
+        .with((Access::new(), |_: &(), world: &World| {
+            world.log.lock().unwrap().push("independent");
+        }));
+
+    let world = World::default();
+    for _ in 0..3 {
+        ports.run(&(), &world);
+    }
+
+    assert_eq!(world.position.load(Ordering::Relaxed), 3);
+    assert_eq!(world.velocity.load(Ordering::Relaxed), 30);
+    assert_eq!(world.log.lock().unwrap().len(), 3);
+}
@@ -0,0 +1,54 @@
+use nuum_core::{event::Propagation, Port};
+
+#[derive(Default)]
+struct Event {
+    handled: bool,
+    seen: Vec<&'static str>,
+}
+
+impl Propagation for Event {
+    fn is_handled(&self) -> bool {
+        self.handled
+    }
+
+    fn set_handled(&mut self) {
+        self.handled = true;
+    }
+}
+
+struct Handle;
+
+impl<'a> Port<'a, Event, ()> for Handle {
+    fn port(&mut self, event: &mut Event, _: &mut ()) {
+        event.seen.push("handle");
+        event.set_handled();
+    }
+}
+
+struct Respecting;
+
+impl<'a> Port<'a, Event, ()> for Respecting {
+    fn port(&mut self, event: &mut Event, _: &mut ()) {
+        event.seen.push("respecting");
+    }
+}
+
+struct Always;
+
+impl<'a> Port<'a, Event, ()> for Always {
+    fn port(&mut self, event: &mut Event, _: &mut ()) {
+        event.seen.push("always");
+    }
+
+    fn respects_handled(&self) -> bool {
+        false
+    }
+}
+
+#[test]
+fn nested_tuples_run_non_respecting_ports() {
+    let mut ports = (Handle, (Respecting, Always), Respecting);
+    let mut event = Event::default();
+    ports.port(&mut event, &mut ());
+    assert_eq!(event.seen, ["handle", "always"]);
+}
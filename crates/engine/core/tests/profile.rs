@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use nuum_core::{
+    event::{FrameEvent, Propagation},
+    profile::{self, ScopeStats, WINDOW},
+    Adapter, Controller,
+};
+
+fn stats(name: &str) -> Option<ScopeStats> {
+    profile::stats().into_iter().find(|s| s.name == name)
+}
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+// The profiler is global, its tests run in a single function so they don't reset each other
+#[test]
+fn profiler() {
+    // Statistics cover the last WINDOW samples, the count covers every sample
+    for i in 1..=300 {
+        profile::record("window", ms(i));
+    }
+    let first = 300 - WINDOW as u64 + 1;
+    assert_eq!(
+        stats("window"),
+        Some(ScopeStats {
+            name: "window".to_owned(),
+            count: 300,
+            min: ms(first),
+            avg: (ms(first) + ms(300)) / 2,
+            max: ms(300),
+            p50: ms(first + (WINDOW as u64 - 1) * 50 / 100),
+            p95: ms(first + (WINDOW as u64 - 1) * 95 / 100),
+            p99: ms(first + (WINDOW as u64 - 1) * 99 / 100),
+        })
+    );
+
+    // Scopes only record once enabled
+    drop(profile::scope("scope"));
+    assert_eq!(stats("scope"), None);
+    profile::enable(true);
+    drop(profile::scope("scope"));
+    profile::enable(false);
+    assert_eq!(stats("scope").map(|s| s.count), Some(1));
+
+    // Sorted by name, with quoted names in csv
+    profile::record("a \"quoted\" name", ms(1));
+    let all = profile::stats();
+    assert!(all.windows(2).all(|w| w[0].name <= w[1].name));
+    let csv = profile::to_csv(&all);
+    assert!(csv.starts_with("name,count,min_us,avg_us,max_us,p50_us,p95_us,p99_us\n"));
+    assert!(csv.contains("\"a \"\"quoted\"\" name\",1,1000,1000,1000,1000,1000,1000\n"));
+
+    // Adapters time their ports once per run, nested ports are not sampled on their own
+    profile::reset();
+    profile::enable(true);
+    let mut app = Adapter::new(((step, step), step), ());
+    for _ in 0..3 {
+        app.run(Event);
+    }
+    profile::enable(false);
+    let all = profile::stats();
+    assert_eq!(all.len(), 1, "{all:?}");
+    assert!(all[0].name.starts_with("(("), "{}", all[0].name);
+    assert_eq!(all[0].count, 3);
+
+    profile::reset();
+    assert!(profile::stats().is_empty());
+}
+
+struct Event;
+
+impl Propagation for Event {}
+
+impl FrameEvent for Event {}
+
+fn step(_: &mut Event, _: &mut ()) {}
@@ -0,0 +1,158 @@
+use nuum_core::{
+    state::{OnEnter, OnExit, StateMachine},
+    Controller, Port,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum State {
+    Menu,
+    Game,
+    Pause,
+}
+
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl Controller<OnEnter<State>> for Log {
+    fn run(&mut self, OnEnter(state): OnEnter<State>) {
+        self.0.push(format!("enter {state:?}"));
+    }
+}
+
+impl Controller<OnExit<State>> for Log {
+    fn run(&mut self, OnExit(state): OnExit<State>) {
+        self.0.push(format!("exit {state:?}"));
+    }
+}
+
+fn log(state: State) -> impl FnMut(&mut (), &mut Log) {
+    move |_, log| log.0.push(format!("run {state:?}"))
+}
+
+fn machine() -> StateMachine<State, (), Log> {
+    StateMachine::new(State::Menu)
+        .with_port(State::Menu, log(State::Menu))
+        .with_port(State::Game, log(State::Game))
+        .with_port(State::Pause, log(State::Pause))
+}
+
+fn run(machine: &mut StateMachine<State, (), Log>) -> Vec<String> {
+    let mut log = Log::default();
+    machine.port(&mut (), &mut log);
+    log.0
+}
+
+#[test]
+fn initial_state_is_entered_on_the_first_event() {
+    let mut machine = machine();
+    assert_eq!(machine.states().current(), None);
+
+    assert_eq!(run(&mut machine), ["enter Menu", "run Menu"]);
+    assert_eq!(run(&mut machine), ["run Menu"]);
+}
+
+#[test]
+fn set_exits_the_current_state() {
+    let mut machine = machine();
+    run(&mut machine);
+
+    machine.states().set(State::Game);
+    assert_eq!(run(&mut machine), ["exit Menu", "enter Game", "run Game"]);
+    assert_eq!(machine.states().stack(), [State::Game]);
+}
+
+#[test]
+fn push_and_pop_keep_the_state_below() {
+    let mut machine = machine();
+    let states = machine.states();
+    states.set(State::Game);
+    run(&mut machine);
+
+    states.push(State::Pause);
+    assert_eq!(run(&mut machine), ["enter Pause", "run Pause"]);
+    assert!(states.contains(&State::Game));
+    assert!(states.is_current(&State::Pause));
+
+    // The state below is entered again without events
+    states.pop();
+    assert_eq!(run(&mut machine), ["exit Pause", "run Game"]);
+    assert_eq!(states.stack(), [State::Game]);
+}
+
+#[test]
+fn overlays_run_above_the_state_below() {
+    let mut machine = machine().with_overlay(State::Pause);
+    let states = machine.states();
+    states.set(State::Game);
+    states.push(State::Pause);
+
+    assert_eq!(
+        run(&mut machine),
+        [
+            "enter Menu",
+            "exit Menu",
+            "enter Game",
+            "enter Pause",
+            "run Pause",
+            "run Game"
+        ]
+    );
+}
+
+#[test]
+fn last_state_is_not_popped() {
+    let mut machine = machine();
+    let states = machine.states();
+    run(&mut machine);
+
+    states.pop();
+    assert_eq!(run(&mut machine), ["run Menu"]);
+    assert_eq!(states.current(), Some(State::Menu));
+}
+
+#[test]
+fn transitions_are_applied_in_request_order() {
+    let mut machine = machine();
+    let states = machine.states();
+    states.set(State::Game);
+    states.push(State::Pause);
+    states.pop();
+
+    let mut log = Log::default();
+    machine.apply(&mut log);
+    assert_eq!(
+        log.0,
+        [
+            "enter Menu",
+            "exit Menu",
+            "enter Game",
+            "enter Pause",
+            "exit Pause"
+        ]
+    );
+}
+
+#[test]
+fn states_covered_by_overlays_stay_active() {
+    let mut machine = machine().with_overlay(State::Pause);
+    let states = machine.states();
+    states.set(State::Game);
+    run(&mut machine);
+    assert!(states.is_active(&State::Game));
+
+    states.push(State::Pause);
+    run(&mut machine);
+    assert!(states.is_active(&State::Pause));
+    assert!(states.is_active(&State::Game));
+    assert!(!states.is_current(&State::Game));
+
+    // States below a regular state are inactive
+    states.push(State::Menu);
+    run(&mut machine);
+    assert!(states.is_active(&State::Menu));
+    assert!(!states.is_active(&State::Pause));
+    assert!(!states.is_active(&State::Game));
+
+    machine.set_overlay(State::Menu, true);
+    assert!(states.is_active(&State::Game));
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use nuum_core::time::Clock;
+
+#[test]
+fn invalid_scales_are_clamped() {
+    let clock = Clock::new();
+    clock.set_scale(-2.);
+    assert_eq!(clock.scale(), 0.);
+    clock.set_scale(f32::NAN);
+    assert_eq!(clock.scale(), 0.);
+    clock.set_scale(f32::INFINITY);
+    assert_eq!(clock.scale(), f32::MAX);
+
+    clock.set_scale(0.);
+    let time = clock.advance(Duration::from_secs(1));
+    assert_eq!(time.delta, Duration::ZERO);
+    assert_eq!(time.unscaled_elapsed, Duration::from_secs(1));
+}
+
+#[test]
+fn scaled_time_saturates() {
+    let clock = Clock::new();
+    clock.set_scale(f32::MAX);
+    let time = clock.advance(Duration::from_secs(1));
+    assert_eq!(time.delta, Duration::MAX);
+
+    let time = clock.advance(Duration::from_secs(1));
+    assert_eq!(time.elapsed, Duration::MAX);
+    assert_eq!(time.unscaled_elapsed, Duration::from_secs(2));
+}
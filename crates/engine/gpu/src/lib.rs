@@ -1,3 +1,4 @@
+use nuum_core::error::{Error, Result};
 use surface::{GpuSurface, SurfaceTarget};
 
 pub use wgpu;
@@ -11,9 +12,16 @@ pub struct Gpu {
 }
 
 impl Gpu {
+    /// Panics if no graphics device is available, see [`Gpu::try_new`].
     pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_new() -> Result<Self> {
+        let _span = tracing::info_span!("gpu_init").entered();
+        let backends = wgpu::Backends::all();
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
+            backends,
             ..Default::default()
         });
 
@@ -22,7 +30,9 @@ impl Gpu {
             compatible_surface: None, //Todo: Add support for webgl
             force_fallback_adapter: false,
         }))
-        .unwrap();
+        .ok_or_else(|| Error::NoAdapter {
+            backends: format!("{backends:?}"),
+        })?;
         let (device, queue) = pollster::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
                 label: None,
@@ -32,29 +42,53 @@ impl Gpu {
             },
             None,
         ))
-        .unwrap_or_else(|e| panic!("Could not acquire graphics device: {e}"));
+        .map_err(|e| Error::RequestDevice {
+            adapter: adapter_name(&adapter),
+            source: e.into(),
+        })?;
 
-        Gpu {
+        let info = adapter.get_info();
+        tracing::info!(
+            adapter = info.name,
+            backend = ?info.backend,
+            driver = info.driver,
+            "Graphics device acquired"
+        );
+
+        Ok(Gpu {
             instance,
             adapter,
             device,
             queue,
-        }
+        })
     }
 
+    /// Panics if the surface cannot be created, see [`Gpu::try_acquire_surface`].
     pub fn acquire_surface<'a>(&self, target: impl Into<SurfaceTarget<'a>>) -> GpuSurface<'a> {
+        self.try_acquire_surface(target)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_acquire_surface<'a>(
+        &self,
+        target: impl Into<SurfaceTarget<'a>>,
+    ) -> Result<GpuSurface<'a>> {
+        let _span = tracing::debug_span!("acquire_surface").entered();
         let target = target.into();
         let surface = self
             .instance
             .create_surface(target.target)
-            .unwrap_or_else(|e| panic!("Could not create graphics surface: {e}"));
+            .map_err(|e| Error::CreateSurface { source: e.into() })?;
         let capabilities = surface.get_capabilities(&self.adapter);
         let format = capabilities
             .formats
             .iter()
             .copied()
             .find(|f| f.is_srgb())
-            .unwrap_or(capabilities.formats[0]);
+            .or(capabilities.formats.first().copied())
+            .ok_or_else(|| Error::IncompatibleSurface {
+                adapter: adapter_name(&self.adapter),
+            })?;
 
         let mut surface = GpuSurface {
             surface,
@@ -64,6 +98,11 @@ impl Gpu {
         };
 
         surface.resize(self, target.size);
-        surface
+        Ok(surface)
     }
 }
+
+fn adapter_name(adapter: &wgpu::Adapter) -> String {
+    let info = adapter.get_info();
+    format!("{} ({:?})", info.name, info.backend)
+}
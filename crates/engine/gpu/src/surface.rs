@@ -19,6 +19,7 @@ impl GpuSurface<'_> {
     pub fn resize(&mut self, gpu: &Gpu, size: impl Into<Vector2<u32>>) {
         let size = size.into();
         self.can_draw = size.x > 0 && size.y > 0;
+        tracing::debug!(width = size.x, height = size.y, format = ?self.format, "Surface configured");
         if self.can_draw {
             self.surface.configure(
                 &gpu.device,
@@ -48,7 +49,7 @@ impl GpuSurface<'_> {
                 wgpu::SurfaceError::OutOfMemory => {
                     panic!("The system is out of memory for rendering!")
                 }
-                _ => (),
+                e => tracing::debug!("Skipped frame, surface texture unavailable: {e}"),
             })
             .ok()?;
 
@@ -0,0 +1,122 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::Parser, parse_quote, parse_quote_spanned, punctuated::Punctuated, spanned::Spanned,
+    visit::Visit, DeriveInput, Field, GenericParam, Lifetime, Token, Type,
+};
+
+use crate::utils::{crate_path, named_fields};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let krate = crate_path(&input.attrs)?;
+    let fields = named_fields(&input, "Controller")?;
+
+    let mut routes: Vec<(Type, Vec<&Field>)> = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("controller"))
+    {
+        for event in parse_events(attr)? {
+            route(&mut routes, event, None);
+        }
+    }
+    for field in &fields.named {
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("forward")) {
+            for event in parse_events(attr)? {
+                route(&mut routes, event, Some(field));
+            }
+        }
+    }
+
+    if routes.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(Controller)] requires events, listed with #[controller(Event, ..)] or #[forward(Event, ..)]",
+        ));
+    }
+
+    let name = &input.ident;
+    let impls = routes.iter().map(|(event, targets)| {
+        let mut generics = input.generics.clone();
+        // Lifetimes named in the event type become parameters of the impl
+        let mut lifetimes = Lifetimes::default();
+        lifetimes.visit_type(event);
+        for lifetime in lifetimes.0 {
+            let declared = generics.lifetimes().any(|l| l.lifetime == lifetime);
+            if !declared && lifetime.ident != "_" && lifetime.ident != "static" {
+                generics
+                    .params
+                    .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+            }
+        }
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+        let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+        for target in targets {
+            let ty = &target.ty;
+            where_clause
+                .predicates
+                .push(parse_quote_spanned! {ty.span()=>
+                    #ty: #krate::Controller<#event>
+                });
+        }
+
+        let last = targets.len().saturating_sub(1);
+        let calls = targets.iter().enumerate().map(|(i, target)| {
+            let ident = &target.ident;
+            if i == last {
+                quote_spanned! {target.ty.span()=>
+                    #krate::Controller::run(&mut self.#ident, event);
+                }
+            } else {
+                quote_spanned! {target.ty.span()=>
+                    #krate::Controller::run(&mut self.#ident, ::core::clone::Clone::clone(&event));
+                }
+            }
+        });
+
+        let event_arg = if targets.is_empty() {
+            quote!(_)
+        } else {
+            quote!(event)
+        };
+        quote! {
+            impl #impl_generics #krate::Controller<#event> for #name #ty_generics #where_clause {
+                fn run(&mut self, #event_arg: #event) {
+                    #(#calls)*
+                }
+            }
+        }
+    });
+
+    Ok(quote! { #(#impls)* })
+}
+
+fn parse_events(attr: &syn::Attribute) -> syn::Result<Punctuated<Type, Token![,]>> {
+    let list = attr.meta.require_list()?;
+    Punctuated::<Type, Token![,]>::parse_terminated.parse2(list.tokens.clone())
+}
+
+fn route<'a>(routes: &mut Vec<(Type, Vec<&'a Field>)>, event: Type, target: Option<&'a Field>) {
+    let index = match routes.iter().position(|(e, _)| *e == event) {
+        Some(index) => index,
+        None => {
+            routes.push((event, Vec::new()));
+            routes.len() - 1
+        }
+    };
+    routes[index].1.extend(target);
+}
+
+#[derive(Default)]
+struct Lifetimes(Vec<Lifetime>);
+
+impl<'ast> Visit<'ast> for Lifetimes {
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        if !self.0.contains(lifetime) {
+            self.0.push(lifetime.clone());
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod controller;
+mod schema;
+mod utils;
+
+/// Routes each event kind to a dedicated port field, with the `#[inner]` field as the ports inner controller.
+///
+/// ```
+/// use nuum_core::{
+///     event::{Render, Update},
+///     time::Time,
+///     Controller, Port, Schema,
+/// };
+///
+/// #[derive(Default)]
+/// struct World {
+///     frames: u32,
+///     steps: u32,
+/// }
+///
+/// struct Renderer;
+///
+/// impl<'a> Port<'a, Render<()>, World> for Renderer {
+///     fn port(&mut self, _: &'a mut Render<()>, world: &mut World) {
+///         world.frames += 1;
+///     }
+/// }
+///
+/// struct Physics;
+///
+/// impl<'a> Port<'a, Update<()>, World> for Physics {
+///     fn port(&mut self, _: &'a mut Update<()>, world: &mut World) {
+///         world.steps += 1;
+///     }
+/// }
+///
+/// #[derive(Schema)]
+/// struct Game {
+///     #[on(Render)]
+///     renderer: Renderer,
+///     #[on(Update)]
+///     physics: Physics,
+///     #[inner]
+///     world: World,
+/// }
+///
+/// let mut game = Game {
+///     renderer: Renderer,
+///     physics: Physics,
+///     world: World::default(),
+/// };
+/// game.run(Update { inner: (), time: Time::default() });
+/// game.run(Update { inner: (), time: Time::default() });
+/// game.run(Render { inner: (), time: Time::default() });
+/// assert_eq!((game.world.steps, game.world.frames), (2, 1));
+/// ```
+///
+/// Implements `Controller<Render<E>>` and `Controller<Update<E>>` for `Game`, for every `E` handled by the ports.
+/// Ports of the same kind run in declaration order, a field can be marked with several kinds and fields without
+/// attributes are left out. Kinds are resolved from the scope of the struct.
+///
+/// Only structs with named fields can derive `Schema`, e.g. not enums or tuple structs:
+///
+/// ```compile_fail
+/// use nuum_core::Schema;
+///
+/// #[derive(Schema)]
+/// struct Game(#[on(Update)] (), #[inner] ());
+/// ```
+///
+/// ```compile_fail
+/// use nuum_core::Schema;
+///
+/// #[derive(Schema)]
+/// enum Game {
+///     Running,
+/// }
+/// ```
+///
+/// A field marked with a kind must be a port of its events, the error points to the field type:
+///
+/// ```compile_fail
+/// use nuum_core::{event::Update, time::Time, Controller, Schema};
+///
+/// #[derive(Schema)]
+/// struct Game {
+///     #[on(Update)]
+///     physics: String,
+///     #[inner]
+///     world: (),
+/// }
+///
+/// let mut game = Game {
+///     physics: String::new(),
+///     world: (),
+/// };
+/// game.run(Update { inner: (), time: Time::default() });
+/// ```
+///
+/// Ports are run with `Port::port_with_bus`. By default each event gets its own
+/// bus, so messages only reach the later ports of the event. A `MessageBus` field marked `#[bus(frame = Kind)]` is
+/// shared by every event instead, and starts a new frame on each event of `Kind`:
+///
+/// ```
+/// use nuum_core::{
+///     bus::{MessageBus, MessageReader},
+///     event::{Render, Update},
+///     time::Time,
+///     Controller, Port, Schema,
+/// };
+///
+/// struct Hit(u32);
+///
+/// struct Physics;
+///
+/// impl<'a> Port<'a, Update<()>, ()> for Physics {
+///     fn port(&mut self, event: &'a mut Update<()>, inner: &mut ()) {
+///         self.port_with_bus(event, inner, &mut MessageBus::new());
+///     }
+///
+///     fn port_with_bus(&mut self, _: &'a mut Update<()>, _: &mut (), bus: &mut MessageBus) {
+///         bus.send(Hit(1));
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Sounds {
+///     hits: MessageReader<Hit>,
+///     played: u32,
+/// }
+///
+/// impl<'a> Port<'a, Render<()>, ()> for Sounds {
+///     fn port(&mut self, event: &'a mut Render<()>, inner: &mut ()) {
+///         self.port_with_bus(event, inner, &mut MessageBus::new());
+///     }
+///
+///     fn port_with_bus(&mut self, _: &'a mut Render<()>, _: &mut (), bus: &mut MessageBus) {
+///         self.played += self.hits.read(bus).map(|hit| hit.0).sum::<u32>();
+///     }
+/// }
+///
+/// #[derive(Schema)]
+/// struct Game {
+///     #[on(Update)]
+///     physics: Physics,
+///     #[on(Render)]
+///     sounds: Sounds,
+///     #[bus(frame = Update)]
+///     bus: MessageBus,
+///     #[inner]
+///     world: (),
+/// }
+///
+/// let mut game = Game {
+///     physics: Physics,
+///     sounds: Sounds::default(),
+///     bus: MessageBus::new(),
+///     world: (),
+/// };
+/// game.run(Update { inner: (), time: Time::default() });
+/// game.run(Render { inner: (), time: Time::default() });
+/// game.run(Render { inner: (), time: Time::default() });
+/// assert_eq!(game.sounds.played, 1);
+/// ```
+///
+/// Generated code refers to `::nuum_core`, use `#[nuum(crate = "nuum::core")]` on the struct to change it.
+#[proc_macro_derive(Schema, attributes(on, inner, bus, nuum))]
+pub fn derive_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    schema::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `Controller<E>` by forwarding each event to the fields marked with it.
+///
+/// ```
+/// use nuum_core::{
+///     event::{Render, Update},
+///     Controller,
+/// };
+///
+/// struct KeyEvent(char);
+///
+/// #[derive(Controller)]
+/// #[controller(Render<&'a mut Vec<u8>>)]
+/// struct App {
+///     #[forward(Update<f32>)]
+///     physics: Physics,
+///     #[forward(Update<f32>, KeyEvent)]
+///     player: Player,
+/// }
+///
+/// struct Physics;
+///
+/// impl Controller<Update<f32>> for Physics {
+///     fn run(&mut self, _: Update<f32>) {}
+/// }
+///
+/// struct Player {
+///     keys: Vec<char>,
+/// }
+///
+/// impl Controller<Update<f32>> for Player {
+///     fn run(&mut self, _: Update<f32>) {}
+/// }
+///
+/// impl Controller<KeyEvent> for Player {
+///     fn run(&mut self, event: KeyEvent) {
+///         self.keys.push(event.0);
+///     }
+/// }
+///
+/// let mut app = App {
+///     physics: Physics,
+///     player: Player { keys: Vec::new() },
+/// };
+/// app.run(KeyEvent('w'));
+/// assert_eq!(app.player.keys, ['w']);
+/// ```
+///
+/// Events listed in `#[controller(..)]` without any field are no-ops. Fields of the same event run in
+/// declaration order, each but the last receiving a clone of the event, so an event forwarded to several
+/// fields must be `Clone`:
+///
+/// ```compile_fail
+/// use nuum_core::Controller;
+///
+/// struct Hit;
+///
+/// struct Health;
+///
+/// impl Controller<Hit> for Health {
+///     fn run(&mut self, _: Hit) {}
+/// }
+///
+/// #[derive(Controller)]
+/// struct Player {
+///     #[forward(Hit)]
+///     health: Health,
+///     #[forward(Hit)]
+///     shield: Health,
+/// }
+/// ```
+///
+/// Lifetimes named in the events are added to the impl generics.
+///
+/// Generated code refers to `::nuum_core`, use `#[nuum(crate = "nuum::core")]` on the struct to change it.
+#[proc_macro_derive(Controller, attributes(controller, forward, nuum))]
+pub fn derive_controller(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    controller::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,140 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, parse_quote_spanned, spanned::Spanned, DeriveInput, Ident, Path};
+
+use crate::utils::{crate_path, named_fields};
+
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let krate = crate_path(&input.attrs)?;
+    let fields = named_fields(&input, "Schema")?;
+
+    let mut inner = None;
+    let mut bus: Option<(&syn::Field, Path)> = None;
+    let mut routes: Vec<(Path, Vec<&syn::Field>)> = Vec::new();
+    for field in &fields.named {
+        for attr in &field.attrs {
+            if attr.path().is_ident("inner") {
+                attr.meta.require_path_only()?;
+                if inner.replace(field).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "only one field can be marked #[inner]",
+                    ));
+                }
+            } else if attr.path().is_ident("bus") {
+                let frame = bus_frame(attr)?;
+                if bus.replace((field, frame)).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "only one field can be marked #[bus]",
+                    ));
+                }
+            } else if attr.path().is_ident("on") {
+                let kind: Path = attr.parse_args()?;
+                match routes.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, ports)) => ports.push(field),
+                    None => routes.push((kind, vec![field])),
+                }
+            }
+        }
+    }
+
+    let Some(inner) = inner else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(Schema)] requires a field marked #[inner], passed to the ports",
+        ));
+    };
+    if routes.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(Schema)] requires at least one field marked #[on(EventKind)]",
+        ));
+    }
+
+    if let Some((field, frame)) = &bus {
+        if !routes.iter().any(|(kind, _)| kind == frame) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                "the frame kind of #[bus] must be routed by a field marked #[on(EventKind)]",
+            ));
+        }
+    }
+
+    let name = &input.ident;
+    let inner_ident = &inner.ident;
+    let inner_ty = &inner.ty;
+    let event = Ident::new("__NuumEvent", name.span());
+
+    let impls = routes.iter().map(|(kind, ports)| {
+        let mut generics = input.generics.clone();
+        generics.params.push(parse_quote!(#event));
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+        let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+        for port in ports {
+            let ty = &port.ty;
+            // Spanned on the field type, so a missing port impl points to the faulty field
+            where_clause.predicates.push(parse_quote_spanned! {ty.span()=>
+                #ty: #krate::PortFor<#kind<#event>, #inner_ty>
+            });
+        }
+
+        // Without a bus field, messages only reach the later ports of the event
+        let setup = match &bus {
+            Some((field, frame)) if frame == kind => {
+                let ident = &field.ident;
+                quote! { self.#ident.next_frame(); }
+            }
+            Some(_) => quote! {},
+            None => quote! { let mut __nuum_bus = #krate::bus::MessageBus::new(); },
+        };
+        let bus_expr = match &bus {
+            Some((field, _)) => {
+                let ident = &field.ident;
+                quote! { &mut self.#ident }
+            }
+            None => quote! { &mut __nuum_bus },
+        };
+
+        let calls = ports.iter().map(|port| {
+            let ident = &port.ident;
+            quote_spanned! {port.ty.span()=>
+                #krate::PortFor::port_for(&mut self.#ident, &mut event, &mut self.#inner_ident, #bus_expr);
+            }
+        });
+
+        quote! {
+            impl #impl_generics #krate::Controller<#kind<#event>> for #name #ty_generics #where_clause {
+                fn run(&mut self, mut event: #kind<#event>) {
+                    #setup
+                    #(#calls)*
+                }
+            }
+        }
+    });
+
+    Ok(quote! { #(#impls)* })
+}
+
+/// Parses the kind starting the frames of the bus, from `#[bus(frame = Kind)]`.
+fn bus_frame(attr: &syn::Attribute) -> syn::Result<Path> {
+    let mut frame = None;
+    if let syn::Meta::List(_) = attr.meta {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("frame") {
+                frame = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported bus option, expected `frame = EventKind`"))
+            }
+        })?;
+    }
+    frame.ok_or_else(|| {
+        syn::Error::new_spanned(
+            attr,
+            "#[bus] requires the kind starting its frames, e.g. #[bus(frame = Update)]",
+        )
+    })
+}
@@ -0,0 +1,33 @@
+use syn::{Attribute, Data, DeriveInput, Fields, FieldsNamed, LitStr, Path};
+
+/// Path to `nuum_core`, overridable with `#[nuum(crate = "...")]`.
+pub fn crate_path(attrs: &[Attribute]) -> syn::Result<Path> {
+    let mut path = syn::parse_quote!(::nuum_core);
+    for attr in attrs.iter().filter(|a| a.path().is_ident("nuum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                path = meta.value()?.parse::<LitStr>()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unknown nuum attribute, expected `crate`"))
+            }
+        })?;
+    }
+    Ok(path)
+}
+
+pub fn named_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<&'a FieldsNamed> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields),
+            _ => Err(syn::Error::new_spanned(
+                &data.fields,
+                format!("#[derive({derive})] requires named fields"),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("#[derive({derive})] can only be used on structs"),
+        )),
+    }
+}
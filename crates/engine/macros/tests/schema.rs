@@ -0,0 +1,198 @@
+use nuum_core::{
+    bus::{MessageBus, MessageReader},
+    event::{Render, Update},
+    time::Time,
+    Controller, Port, Schema,
+};
+
+/// Records the port runs, `T` tells the events apart.
+#[derive(Default)]
+struct World<T> {
+    log: Vec<String>,
+    seen: Vec<T>,
+}
+
+struct Named(&'static str);
+
+impl<'a, T: Clone> Port<'a, Update<T>, World<T>> for Named {
+    fn port(&mut self, event: &'a mut Update<T>, world: &mut World<T>) {
+        world.log.push(format!("update {}", self.0));
+        world.seen.push(event.inner.clone());
+    }
+}
+
+impl<'a, T> Port<'a, Render<T>, World<T>> for Named {
+    fn port(&mut self, _: &'a mut Render<T>, world: &mut World<T>) {
+        world.log.push(format!("render {}", self.0));
+    }
+}
+
+/// Not routed, left out of the generated impls.
+struct Settings {
+    volume: f32,
+}
+
+#[derive(Schema)]
+struct Game<T> {
+    #[on(Update)]
+    #[on(Render)]
+    player: Named,
+    settings: Settings,
+    /// Nested ports run in order, as a single field.
+    #[on(Update)]
+    systems: (Named, Named),
+    #[on(Render)]
+    ui: Named,
+    #[inner]
+    world: World<T>,
+}
+
+fn update<T>(inner: T) -> Update<T> {
+    Update {
+        inner,
+        time: Time::default(),
+    }
+}
+
+#[test]
+fn events_are_routed_to_the_marked_fields() {
+    let mut game = Game {
+        player: Named("player"),
+        settings: Settings { volume: 0.5 },
+        systems: (Named("physics"), Named("ai")),
+        ui: Named("ui"),
+        world: World::default(),
+    };
+
+    game.run(update(1u8));
+    game.run(Render {
+        inner: 2u8,
+        time: Time::default(),
+    });
+
+    assert_eq!(
+        game.world.log,
+        [
+            "update player",
+            "update physics",
+            "update ai",
+            "render player",
+            "render ui"
+        ]
+    );
+    assert_eq!(game.world.seen, [1, 1, 1]);
+    assert_eq!(game.settings.volume, 0.5);
+}
+
+#[derive(Schema)]
+#[nuum(crate = "nuum_core")]
+struct Renamed {
+    #[on(Update)]
+    player: Named,
+    #[inner]
+    world: World<&'static str>,
+}
+
+#[test]
+fn crate_path_can_be_renamed() {
+    let mut game = Renamed {
+        player: Named("player"),
+        world: World::default(),
+    };
+
+    game.run(update("step"));
+    assert_eq!(game.world.log, ["update player"]);
+    assert_eq!(game.world.seen, ["step"]);
+}
+
+/// Sends its event on the bus and reads the messages sent since its previous run.
+#[derive(Default)]
+struct Echo(MessageReader<u32>);
+
+impl<'a> Port<'a, Update<u32>, Vec<Vec<u32>>> for Echo {
+    fn port(&mut self, event: &'a mut Update<u32>, inner: &mut Vec<Vec<u32>>) {
+        self.port_with_bus(event, inner, &mut MessageBus::new());
+    }
+
+    fn port_with_bus(
+        &mut self,
+        event: &'a mut Update<u32>,
+        _: &mut Vec<Vec<u32>>,
+        bus: &mut MessageBus,
+    ) {
+        bus.send(event.inner);
+    }
+}
+
+impl<'a> Port<'a, Render<u32>, Vec<Vec<u32>>> for Echo {
+    fn port(&mut self, event: &'a mut Render<u32>, inner: &mut Vec<Vec<u32>>) {
+        self.port_with_bus(event, inner, &mut MessageBus::new());
+    }
+
+    fn port_with_bus(
+        &mut self,
+        _: &'a mut Render<u32>,
+        read: &mut Vec<Vec<u32>>,
+        bus: &mut MessageBus,
+    ) {
+        read.push(self.0.read(bus).copied().collect());
+    }
+}
+
+#[derive(Schema)]
+struct Shared {
+    #[on(Update)]
+    #[on(Render)]
+    echo: Echo,
+    #[bus(frame = Update)]
+    bus: MessageBus,
+    #[inner]
+    read: Vec<Vec<u32>>,
+}
+
+#[derive(Schema)]
+struct Detached {
+    #[on(Update)]
+    #[on(Render)]
+    echo: Echo,
+    #[inner]
+    read: Vec<Vec<u32>>,
+}
+
+fn render(inner: u32) -> Render<u32> {
+    Render {
+        inner,
+        time: Time::default(),
+    }
+}
+
+#[test]
+fn bus_field_is_shared_by_the_events() {
+    let mut game = Shared {
+        echo: Echo::default(),
+        bus: MessageBus::new(),
+        read: Vec::new(),
+    };
+    game.run(update(1));
+    game.run(update(2));
+    game.run(render(0));
+    game.run(update(3));
+    game.run(update(4));
+    game.run(update(5));
+    game.run(render(0));
+
+    // Frames start on updates, 3 was dropped before being read
+    assert_eq!(game.read, [vec![1, 2], vec![4, 5]]);
+}
+
+#[test]
+fn events_get_their_own_bus_without_a_bus_field() {
+    let mut game = Detached {
+        echo: Echo::default(),
+        read: Vec::new(),
+    };
+    game.run(update(1));
+    game.run(render(0));
+
+    assert_eq!(game.read, [Vec::<u32>::new()]);
+}
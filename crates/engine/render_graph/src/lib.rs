@@ -1,4 +1,8 @@
 use dagga::{Node, Schedule};
+use nuum_core::{
+    error::{Error, Result},
+    profile,
+};
 use nuum_gpu::{surface::Frame, Gpu};
 use pass::{DynPass, PassEncoder, PassNode};
 use res::{RenderGraphAlloc, RenderResMap, ResId};
@@ -30,7 +34,10 @@ impl RenderGraph {
 
         for batch in &mut self.schedule.batches {
             for node in batch {
-                (node.inner_mut().run)(&self.data, &mut encoder, gpu);
+                let DynPass { name, run } = node.inner_mut();
+                let _span = tracing::trace_span!("pass", name).entered();
+                let _scope = profile::scope(name);
+                run(&self.data, &mut encoder, gpu);
             }
         }
 
@@ -51,19 +58,28 @@ pub struct RenderGraphBuilder {
 
 impl RenderGraphBuilder {
     pub fn with_pass(mut self, name: impl Into<String>, pass: impl PassEncoder) -> Self {
+        let name = name.into();
         let builder = pass.node_builder();
-        let node = Node::new(pass.dyn_pass()).with_name(name);
+        let mut dyn_pass = pass.dyn_pass();
+        dyn_pass.name.clone_from(&name);
+        let node = Node::new(dyn_pass).with_name(name);
         self.dag.add_node(builder(node));
         self
     }
 
+    /// Panics if the passes cannot be scheduled, see [`RenderGraphBuilder::try_build`].
     pub fn build(self, alloc: RenderGraphAlloc) -> RenderGraph {
-        RenderGraph {
+        self.try_build(alloc).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    #[tracing::instrument(name = "render_graph_build", skip_all)]
+    pub fn try_build(self, alloc: RenderGraphAlloc) -> Result<RenderGraph> {
+        Ok(RenderGraph {
             schedule: self
                 .dag
                 .build_schedule()
-                .unwrap_or_else(|e| panic!("Failed to build render graph: {e}")),
+                .map_err(|e| Error::RenderGraph { source: e.into() })?,
             data: RenderResMap::not_ready(alloc),
-        }
+        })
     }
 }
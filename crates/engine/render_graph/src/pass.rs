@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 
 use dagga::Node;
+use nuum_core::state::States;
 use nuum_gpu::{wgpu::CommandEncoder, Gpu};
 
 use crate::res::{RenderResMap, ResId};
@@ -13,18 +14,29 @@ pub trait PassEncoder: Sized + 'static {
 
     fn dyn_pass(mut self) -> DynPass {
         DynPass {
+            name: String::new(),
             run: Box::new(move |res, enc, gpu| self.encode(res, enc, gpu)),
         }
     }
 }
 
 pub struct DynPass {
+    pub name: String,
     pub run: Box<dyn FnMut(&RenderResMap, &mut CommandEncoder, &Gpu)>,
 }
 
 pub trait PassScheduler: PassEncoder {
     fn run_before(self, name: impl Into<Cow<'static, str>>) -> RunBefore<Self>;
     fn run_after(self, name: impl Into<Cow<'static, str>>) -> RunAfter<Self>;
+    /// Skips the pass on frames where `condition` is false, e.g. outside of an app state.
+    fn run_if(self, condition: impl FnMut() -> bool + 'static) -> RunIf<Self>;
+    /// Skips the pass on frames where `state` is not active in `states`, see [`States::is_active`]. The pass of a
+    /// state covered by an overlay keeps running, e.g. the game rendered behind a pause menu.
+    fn run_in_state<S: Clone + PartialEq + 'static>(
+        self,
+        states: States<S>,
+        state: S,
+    ) -> RunIf<Self>;
 }
 
 impl<T: PassEncoder> PassScheduler for T {
@@ -35,10 +47,23 @@ impl<T: PassEncoder> PassScheduler for T {
     fn run_after(self, name: impl Into<Cow<'static, str>>) -> RunAfter<Self> {
         RunAfter(self, name.into())
     }
+
+    fn run_if(self, condition: impl FnMut() -> bool + 'static) -> RunIf<Self> {
+        RunIf(self, Box::new(condition))
+    }
+
+    fn run_in_state<S: Clone + PartialEq + 'static>(
+        self,
+        states: States<S>,
+        state: S,
+    ) -> RunIf<Self> {
+        self.run_if(move || states.is_active(&state))
+    }
 }
 
 pub struct RunBefore<T: PassEncoder>(pub T, Cow<'static, str>);
 pub struct RunAfter<T: PassEncoder>(pub T, Cow<'static, str>);
+pub struct RunIf<T: PassEncoder>(pub T, Box<dyn FnMut() -> bool>);
 
 impl<T: PassEncoder> PassEncoder for RunBefore<T> {
     fn encode(&mut self, res: &RenderResMap, encoder: &mut CommandEncoder, gpu: &Gpu) {
@@ -63,3 +88,56 @@ impl<T: PassEncoder> PassEncoder for RunAfter<T> {
         move |node| node_builder(node).run_after(name)
     }
 }
+
+impl<T: PassEncoder> PassEncoder for RunIf<T> {
+    fn encode(&mut self, res: &RenderResMap, encoder: &mut CommandEncoder, gpu: &Gpu) {
+        if (self.1)() {
+            self.0.encode(res, encoder, gpu);
+        }
+    }
+
+    fn node_builder(&self) -> (impl FnOnce(PassNode) -> PassNode + 'static) {
+        self.0.node_builder()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nuum_core::state::StateMachine;
+
+    use super::*;
+
+    struct Noop;
+
+    impl PassEncoder for Noop {
+        fn encode(&mut self, _: &RenderResMap, _: &mut CommandEncoder, _: &Gpu) {}
+
+        fn node_builder(&self) -> (impl FnOnce(PassNode) -> PassNode + 'static) {
+            |node| node
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum State {
+        Game,
+        Pause,
+    }
+
+    #[test]
+    fn passes_run_behind_overlays() {
+        let mut machine =
+            StateMachine::<State, (), ()>::new(State::Game).with_overlay(State::Pause);
+        let states = machine.states();
+        let mut game = Noop.run_in_state(states.clone(), State::Game);
+        let mut pause = Noop.run_in_state(states.clone(), State::Pause);
+
+        machine.apply(&mut ());
+        assert!((game.1)());
+        assert!(!(pause.1)());
+
+        states.push(State::Pause);
+        machine.apply(&mut ());
+        assert!((game.1)());
+        assert!((pause.1)());
+    }
+}
@@ -1,7 +1,8 @@
 use std::{
-    any::{type_name, Any},
+    any::{type_name, Any, TypeId},
     borrow::BorrowMut,
     cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
     marker::PhantomData,
     usize, vec,
 };
@@ -179,6 +180,27 @@ impl RenderGraphAlloc {
     }
 }
 
+/// Resource handles indexed by their type, to share resources between independently built passes. <br/>
+/// Only one handle per resource type can be registered.
+#[derive(Default)]
+pub struct ResRegistry {
+    handles: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl ResRegistry {
+    pub fn insert<T: 'static>(&mut self, handle: ResHandle<T>) -> Option<ResHandle<T>> {
+        self.handles
+            .insert(TypeId::of::<T>(), Box::new(handle))
+            .map(|h| *h.downcast().unwrap())
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&ResHandle<T>> {
+        self.handles
+            .get(&TypeId::of::<T>())
+            .map(|h| h.downcast_ref().unwrap())
+    }
+}
+
 pub struct RenderResMap {
     alloc: RenderGraphAlloc,
 }
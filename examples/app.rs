@@ -0,0 +1,40 @@
+use std::time::Instant;
+
+use nuum::{egui::api, gpu::wgpu::Color, prelude::*};
+
+struct State {
+    start: Instant,
+    background_color: [f32; 3],
+}
+
+fn main() {
+    #[cfg(feature = "subscriber")]
+    nuum::trace::init();
+
+    App::new(State {
+        start: Instant::now(),
+        background_color: [0.0, 0.0, 0.0],
+    })
+    .with_window(WindowAttributes::default().with_title("Nuum app"))
+    .with_ui(|state, ctx| {
+        api::Window::new("Nuum EGUI window").show(ctx, |ui| {
+            ui.heading(format!(
+                "Hello world! {}",
+                state.start.elapsed().as_millis()
+            ));
+            ui.separator();
+            ui.label("Background color:");
+            ui.color_edit_button_rgb(&mut state.background_color);
+        });
+    })
+    .with_render(|state, event| {
+        // The clear color resource is registered by the builtin render plugin
+        let mut color = event
+            .render_data()
+            .access(|res| res.get::<Color>().unwrap().write());
+        color.r = state.background_color[0] as f64;
+        color.g = state.background_color[1] as f64;
+        color.b = state.background_color[2] as f64;
+    })
+    .run();
+}
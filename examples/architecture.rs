@@ -31,10 +31,7 @@ impl Controller<WorldRenderEvent> for World {
 }
 
 fn main() {
-    let mut app = Adapter {
-        ports: (UpdatePort, RenderPort),
-        inner: World,
-    };
+    let mut app = Adapter::new((UpdatePort, RenderPort), World);
 
     app.run(());
 }
@@ -18,20 +18,21 @@ use nuum_event_loop::{EventLoopPort, UpdateEvent};
 use nuum_renderer::{IsRenderEvent, RenderEvent, RenderPort};
 
 fn main() {
-    //impl_schema!(Schema := Render);
-
-    let mut app = Adapter {
-        ports: (
+    let mut app = Adapter::new(
+        (
             SingleWindowPort::default(),
             RenderPort::new_with_native(render_graph, EguiRenderer::default()),
             EventLoopPort::default(),
         ),
-        inner: App::default(),
-    };
+        App::default(),
+    );
 
-    WinPlatform::default().run(&mut app);
+    WinPlatform::new().run(&mut app);
 }
 
+#[derive(Controller)]
+#[controller(RenderEvent<'a, RenderData>, UpdateEvent)]
+#[nuum(crate = "nuum::core")]
 struct App {
     start: Instant,
     background_color: [f32; 3],
@@ -62,14 +63,6 @@ impl<'a, 'b> Controller<RenderEguiEvent<'a, 'b, RenderData>> for App {
     }
 }
 
-impl<'a> Controller<RenderEvent<'a, RenderData>> for App {
-    fn run(&mut self, _: RenderEvent<'a, RenderData>) {}
-}
-
-impl<'a> Controller<UpdateEvent> for App {
-    fn run(&mut self, _: UpdateEvent) {}
-}
-
 pub struct RenderData {
     egui: ResHandle<EguiRenderPayload>,
     background: ResHandle<Color>,
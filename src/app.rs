@@ -0,0 +1,303 @@
+use std::path::PathBuf;
+
+use nuum_core::{error::Result, profile, time::Clock, Controller};
+use nuum_egui::{api::Context, plugin::EguiPlugin, RenderEguiEvent};
+use nuum_event_loop::{EventLoopPort, UpdateEvent};
+use nuum_gpu::wgpu::Color;
+use nuum_plugin::{
+    builtins::{RenderPlugin, UpdatePlugin, WindowPlugin},
+    Plugin, PluginApp, PluginContext, Plugins, RenderGraphContext,
+};
+use nuum_render_graph::res::ResRegistry;
+use nuum_renderer::{FrameLimit, RedrawHandle, RedrawMode, RenderEvent};
+use nuum_win_platform::{
+    window_manager::{WindowClosed, WindowOpened, WindowSpec},
+    winit::window::WindowAttributes,
+    WinPlatform,
+};
+
+type UpdateFn<S> = Box<dyn FnMut(&mut S, &UpdateEvent)>;
+type RenderFn<S> = Box<dyn for<'a> FnMut(&mut S, &mut RenderEvent<'a, ResRegistry>)>;
+type UiFn<S> = Box<dyn FnMut(&mut S, &Context)>;
+type RenderGraphFn = Box<dyn FnMut(&mut RenderGraphContext)>;
+
+/// High level app builder, assembling the builtin plugins around a user state `S`.
+///
+/// ```no_run
+/// nuum::App::new(0u64)
+///     .with_egui()
+///     .with_update(|frames, _| *frames += 1)
+///     .with_ui(|frames, ctx| {
+///         nuum::egui::api::Window::new("Stats").show(ctx, |ui| ui.label(format!("{frames} updates")));
+///     })
+///     .run();
+/// ```
+pub struct App<S: 'static = ()> {
+    state: S,
+    windows: Vec<WindowSpec>,
+    clear_color: Color,
+    egui: bool,
+    clock: Clock,
+    redraw_mode: RedrawMode,
+    redraw: RedrawHandle,
+    frame_limit: Option<FrameLimit>,
+    profile: Option<PathBuf>,
+    render_graph: Vec<RenderGraphFn>,
+    plugins: Plugins<AppState<S>>,
+
+    update: Vec<UpdateFn<S>>,
+    render: Vec<RenderFn<S>>,
+    ui: Vec<UiFn<S>>,
+}
+
+impl<S: Default + 'static> Default for App<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: 'static> App<S> {
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            windows: vec![WindowSpec::new(
+                "main",
+                WindowAttributes::default().with_title("Nuum window"),
+            )],
+            clear_color: Color::BLACK,
+            egui: false,
+            clock: Clock::new(),
+            redraw_mode: RedrawMode::default(),
+            redraw: RedrawHandle::new(),
+            frame_limit: None,
+            profile: None,
+            render_graph: Vec::new(),
+            plugins: Plugins::new(),
+            update: Vec::new(),
+            render: Vec::new(),
+            ui: Vec::new(),
+        }
+    }
+
+    /// Replaces the windows with a single one named `"main"`.
+    pub fn with_window(self, attrs: WindowAttributes) -> Self {
+        self.with_windows([WindowSpec::new("main", attrs)])
+    }
+
+    /// Replaces the windows, each one is rendered with the render graph, see [`WindowManagerPort`].
+    ///
+    /// [`WindowManagerPort`]: nuum_win_platform::window_manager::WindowManagerPort
+    pub fn with_windows(mut self, specs: impl IntoIterator<Item = WindowSpec>) -> Self {
+        self.windows = specs.into_iter().collect();
+        self
+    }
+
+    pub fn with_clear_color(mut self, color: Color) -> Self {
+        self.clear_color = color;
+        self
+    }
+
+    /// Adds passes and resources to the render graph of the windows, after the builtin `"clear"` pass.
+    pub fn with_render_graph(mut self, f: impl FnMut(&mut RenderGraphContext) + 'static) -> Self {
+        self.render_graph.push(Box::new(f));
+        self
+    }
+
+    /// Drives updates with `clock`, keep a clone in the state to pause or scale the game time. <br/>
+    /// Frames keep their own clock, the clock must not be passed to another driver, see [`Clock`].
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// [`RedrawMode::Reactive`] only redraws on input or when asked to, for tools idling most of the time.
+    pub fn with_redraw_mode(mut self, mode: RedrawMode) -> Self {
+        self.redraw_mode = mode;
+        self
+    }
+
+    /// Redraws are requested through `redraw`, keep a clone in the state to invalidate the window.
+    pub fn with_redraw_handle(mut self, redraw: RedrawHandle) -> Self {
+        self.redraw = redraw;
+        self
+    }
+
+    /// Caps the frame rate of the windows, e.g. `FrameLimit::fps(60.)`.
+    pub fn with_frame_limit(mut self, limit: FrameLimit) -> Self {
+        self.frame_limit = Some(limit);
+        self
+    }
+
+    /// Profiles ports and render passes, dumping the statistics to `path` at exit, see [`profile::dump`].
+    pub fn with_profiler(mut self, path: impl Into<PathBuf>) -> Self {
+        self.profile = Some(path.into());
+        self
+    }
+
+    pub fn with_egui(mut self) -> Self {
+        self.egui = true;
+        self
+    }
+
+    /// User plugins are built after the builtin ones, so their ports run after them, e.g. behind the egui ui.
+    pub fn with_plugin(mut self, plugin: impl Plugin<AppState<S>> + 'static) -> Self {
+        self.plugins = self.plugins.with_plugin(plugin);
+        self
+    }
+
+    pub fn with_update(mut self, f: impl FnMut(&mut S, &UpdateEvent) + 'static) -> Self {
+        self.update.push(Box::new(f));
+        self
+    }
+
+    pub fn with_render(
+        mut self,
+        f: impl for<'a> FnMut(&mut S, &mut RenderEvent<'a, ResRegistry>) + 'static,
+    ) -> Self {
+        self.render.push(Box::new(f));
+        self
+    }
+
+    /// Draws egui ui every frame, enables egui.
+    pub fn with_ui(mut self, f: impl FnMut(&mut S, &Context) + 'static) -> Self {
+        self.ui.push(Box::new(f));
+        self.with_egui()
+    }
+
+    /// Panics if the plugins or the event loop fail, see [`App::try_run`].
+    pub fn run(self) -> S {
+        self.try_run().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_run(mut self) -> Result<S> {
+        let _profile = self.profile.take().map(profile::DumpOnDrop::new);
+        let mut app = self.try_build()?;
+        WinPlatform::new().try_run(&mut app)?;
+
+        Ok(app.inner.state)
+    }
+
+    /// Builds the builtin plugins first, so their ports run before the ports of the user plugins.
+    fn try_build(self) -> Result<PluginApp<AppState<S>>> {
+        let mut plugins = Plugins::new()
+            .with_plugin(WindowPlugin::with_windows(self.windows))
+            .with_plugin(RenderPlugin {
+                clear_color: self.clear_color,
+                redraw_mode: self.redraw_mode,
+                redraw: self.redraw,
+                frame_limit: self.frame_limit,
+            })
+            .with_plugin(UserRenderGraph(self.render_graph))
+            .with_plugin(UpdatePlugin(
+                EventLoopPort::default().with_clock(self.clock),
+            ));
+        if self.egui {
+            plugins = plugins.with_plugin(EguiPlugin);
+        }
+
+        plugins.with_plugins(self.plugins).try_build(AppState {
+            state: self.state,
+            update: self.update,
+            render: self.render,
+            ui: self.ui,
+        })
+    }
+}
+
+/// Inner controller of an [`App`], dispatching events to the user callbacks.
+pub struct AppState<S> {
+    pub state: S,
+    update: Vec<UpdateFn<S>>,
+    render: Vec<RenderFn<S>>,
+    ui: Vec<UiFn<S>>,
+}
+
+impl<S> Controller<WindowOpened> for AppState<S> {
+    fn run(&mut self, _: WindowOpened) {}
+}
+
+impl<S> Controller<WindowClosed> for AppState<S> {
+    fn run(&mut self, _: WindowClosed) {}
+}
+
+impl<S> Controller<UpdateEvent> for AppState<S> {
+    fn run(&mut self, event: UpdateEvent) {
+        for f in &mut self.update {
+            f(&mut self.state, &event);
+        }
+    }
+}
+
+impl<'a, S> Controller<RenderEvent<'a, ResRegistry>> for AppState<S> {
+    fn run(&mut self, mut event: RenderEvent<'a, ResRegistry>) {
+        for f in &mut self.render {
+            f(&mut self.state, &mut event);
+        }
+    }
+}
+
+impl<'a, 'b, S> Controller<RenderEguiEvent<'a, 'b, ResRegistry>> for AppState<S> {
+    fn run(&mut self, event: RenderEguiEvent<'a, 'b, ResRegistry>) {
+        for f in &mut self.ui {
+            f(&mut self.state, &event.egui);
+        }
+    }
+}
+
+struct UserRenderGraph(Vec<RenderGraphFn>);
+
+impl<Inner: 'static> Plugin<Inner> for UserRenderGraph {
+    fn dependencies(&self) -> Vec<&'static str> {
+        vec![RenderPlugin::name()]
+    }
+
+    fn build(self: Box<Self>, ctx: &mut PluginContext<Inner>) {
+        for f in self.0 {
+            ctx.add_render_graph(f);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::type_name;
+
+    use nuum_core::Port;
+    use nuum_win_platform::WinPlatformEvent;
+
+    use super::*;
+
+    struct GamePort;
+
+    impl<'a, Inner> Port<'a, WinPlatformEvent<'_>, Inner> for GamePort {
+        fn port(&mut self, _: &'a mut WinPlatformEvent<'_>, _: &mut Inner) {}
+    }
+
+    struct GamePlugin;
+
+    impl<Inner: 'static> Plugin<Inner> for GamePlugin {
+        fn build(self: Box<Self>, ctx: &mut PluginContext<Inner>) {
+            ctx.add_port(GamePort);
+        }
+    }
+
+    /// Events handled by egui are skipped by the ports running after the `RenderPort`, which holds the egui renderer.
+    #[test]
+    fn user_ports_run_after_the_egui_input() {
+        let app = App::new(())
+            .with_plugin(GamePlugin)
+            .with_egui()
+            .try_build()
+            .expect("A GPU is required to build the render port");
+
+        let names: Vec<_> = app
+            .ports
+            .ids()
+            .filter_map(|id| app.ports.name(id))
+            .collect();
+        let position = |name: &str| names.iter().position(|n| n.starts_with(name));
+        let render = position("nuum_renderer::RenderPort<");
+        let game = position(type_name::<GamePort>());
+        assert!(render.unwrap() < game.unwrap(), "{names:?}");
+    }
+}
@@ -1,4 +1,5 @@
 pub use nuum_core as core;
+pub use nuum_core::error::{Error, Result};
 
 pub mod platform {
     #[cfg(feature = "win_platform")]
@@ -10,31 +11,41 @@ pub use nuum_gpu as gpu;
 #[cfg(feature = "renderer")]
 pub use nuum_render_graph as render_graph;
 
-/*
-pub mod compat {
-    use std::borrow::Borrow;
+pub use nuum_egui as egui;
+pub use nuum_event_loop as event_loop;
+pub use nuum_hot_reload as hot_reload;
+pub use nuum_plugin as plugin;
+pub use nuum_renderer as renderer;
+pub use nuum_tasks as tasks;
 
-    #[cfg(feature = "gpu")]
-    pub trait IntoSurfaceTarget<'a>: 'a {
-        fn into_surface_target(self) -> super::gpu::surface::SurfaceTarget<'a>;
-    }
+pub mod trace;
 
-    #[cfg(all(feature = "win_platform", feature = "gpu"))]
-    impl<
-            'a,
-            T: Borrow<super::platform::win::winit::window::Window>
-                + Into<super::gpu::surface::RawSurfaceTarget<'a>>
-                + 'a,
-        > IntoSurfaceTarget<'a> for T
-    {
-        fn into_surface_target(self) -> super::gpu::surface::SurfaceTarget<'a> {
-            let win = self.borrow();
-            let (w, h) = win.inner_size().into();
-            super::gpu::surface::SurfaceTarget {
-                size: [w, h].into(),
-                target: Into::<_>::into(self),
-            }
-        }
-    }
+#[cfg(all(feature = "win_platform", feature = "gpu", feature = "renderer"))]
+mod app;
+#[cfg(all(feature = "win_platform", feature = "gpu", feature = "renderer"))]
+pub use app::{App, AppState};
+
+pub mod prelude {
+    pub use crate::core::{
+        platform::Platform,
+        time::{Clock, Time},
+        Adapter, Controller, Port,
+    };
+    pub use crate::event_loop::{
+        EventLoopPort, TimerEvent, TimerPort, Timers, UpdateEvent, UpdateMode,
+    };
+    pub use crate::plugin::{Plugin, PluginContext, Plugins, RenderGraphContext};
+    pub use crate::renderer::{
+        FrameLimit, IsRenderEvent, RedrawHandle, RedrawMode, RenderEvent, RenderPort,
+    };
+    pub use crate::tasks::{Spawner, TaskPort};
+
+    #[cfg(feature = "win_platform")]
+    pub use crate::platform::win::{
+        window_manager::WindowSpec, winit::window::WindowAttributes, WinPlatform,
+    };
+    #[cfg(feature = "renderer")]
+    pub use crate::render_graph::{pass::PassScheduler, res::ResRegistry};
+    #[cfg(all(feature = "win_platform", feature = "gpu", feature = "renderer"))]
+    pub use crate::App;
 }
- */
@@ -0,0 +1,17 @@
+pub use tracing;
+
+/// Installs a global subscriber printing engine logs to stderr, filtered by `RUST_LOG` (defaults to `info`). <br/>
+/// Closed spans are logged with their duration, e.g. `RUST_LOG=nuum_renderer=debug` times every frame. Use the
+/// `tracing` ecosystem directly for other outputs, e.g. profilers.
+///
+/// Does nothing if a global subscriber is already set.
+#[cfg(feature = "subscriber")]
+pub fn init() {
+    use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .try_init();
+}